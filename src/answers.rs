@@ -0,0 +1,270 @@
+//! The registry of recorded answers in `answers.toml`, and the `verify`
+//! command that checks the solutions against it.
+//!
+//! Every entry is keyed by day, part and input file. It records the accepted
+//! answer, if it is known, and the guesses that were rejected:
+//!
+//! ```toml
+//! [[answer]]
+//! day = 9
+//! part = 2
+//! input = "day9.txt"
+//! expected = 2607
+//! wrong = [2612]
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::VerifyArgs;
+use crate::error::{Error, Result};
+use crate::runner::{days, default_inputs, solution, solve, SolvedPart};
+use crate::solution::Answer;
+use crate::util::*;
+
+/// The contents of an answers file.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    pub entries: Vec<Entry>,
+}
+
+/// What is known about the answer for one day, part and input.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    /// The accepted answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Recorded>,
+    /// Answers that were rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<Recorded>,
+}
+
+/// An answer as it is written in the answers file. Grids are written as
+/// multi-line strings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Recorded {
+    Int(i64),
+    Str(String),
+}
+
+impl Recorded {
+    pub fn matches(&self, answer: &Answer) -> bool {
+        match (self, answer) {
+            (Recorded::Int(expected), Answer::Int(actual)) => expected == actual,
+            (Recorded::Str(expected), _) => expected.trim_end_matches('\n') == answer.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Recorded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recorded::Int(value) => write!(f, "{}", value),
+            Recorded::Str(value) => write!(f, "{}", value.trim_end_matches('\n')),
+        }
+    }
+}
+
+impl Answers {
+    /// The default location of the answers file, next to the inputs.
+    pub fn default_path() -> PathBuf {
+        year_dir().join("answers.toml")
+    }
+
+    /// Load an answers file. A file that doesn't exist has no entries.
+    pub fn load(path: &Path) -> Result<Answers> {
+        load_toml(path)
+    }
+
+    /// The entry for a day, part and input, if there is one.
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part && entry.input == input)
+    }
+
+    /// The inputs of a day that are either run by default or have an entry.
+    fn inputs(&self, day: u8) -> Vec<String> {
+        let mut inputs = default_inputs(day);
+        for entry in &self.entries {
+            if entry.day == day && !inputs.contains(&entry.input) {
+                inputs.push(entry.input.clone());
+            }
+        }
+        inputs
+    }
+}
+
+/// The outcome of checking one answer against the answers file.
+#[derive(Debug)]
+pub enum Status {
+    /// The answer is the recorded answer.
+    Pass,
+    /// The answer differs from the recorded answer.
+    Fail { expected: Recorded, actual: Answer },
+    /// The answer was already rejected once.
+    KnownWrong(Answer),
+    /// There is no recorded answer to compare to.
+    Missing(Answer),
+    /// The solution could not be run.
+    Error(Error),
+}
+
+impl Status {
+    pub fn check(entry: Option<&Entry>, answer: Answer) -> Status {
+        let Some(entry) = entry else {
+            return Status::Missing(answer);
+        };
+        if entry.wrong.iter().any(|wrong| wrong.matches(&answer)) {
+            return Status::KnownWrong(answer);
+        }
+        match &entry.expected {
+            Some(expected) if expected.matches(&answer) => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+                actual: answer,
+            },
+            None => Status::Missing(answer),
+        }
+    }
+}
+
+impl Status {
+    /// A short name for machine readable output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "fail",
+            Status::KnownWrong(_) => "known_wrong",
+            Status::Missing(_) => "missing",
+            Status::Error(_) => "error",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL, expected {} but got {}", expected, actual)
+            }
+            Status::KnownWrong(answer) => write!(f, "FAIL, {} is a known wrong answer", answer),
+            Status::Missing(answer) => write!(f, "missing, got {}", answer),
+            Status::Error(err) => write!(f, "ERROR, {}", err),
+        }
+    }
+}
+
+/// Counts of the statuses of a `verify` run.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    fn add(&mut self, status: &Status) {
+        match status {
+            Status::Pass => self.passed += 1,
+            Status::Missing(_) => self.missing += 1,
+            _ => self.failed += 1,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Run every solution and compare the answers to the answers file, printing
+/// one line per day, part and input.
+pub fn verify(args: &VerifyArgs) -> Result<Summary> {
+    let path = args.answers.clone().unwrap_or_else(Answers::default_path);
+    let answers = Answers::load(&path)?;
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days(year()).to_vec(),
+    };
+
+    let mut summary = Summary::default();
+    for day in days {
+        let solution = solution(year(), day).ok_or(Error::UnknownDay(day))?;
+        for input in answers.inputs(day) {
+            let solved = match solve(solution, &input, None) {
+                Ok(solved) => solved,
+                Err(err) => {
+                    // Both parts fail if the input can't be read or parsed.
+                    summary.failed += 2;
+                    println!("Day {} ({}): {}", day, input, Status::Error(err));
+                    continue;
+                }
+            };
+            for SolvedPart { part, answer, .. } in solved.parts {
+                let status = Status::check(answers.get(day, part, &input), answer);
+                summary.add(&status);
+                println!("Day {}, Part {} ({}): {}", day, part, input, status);
+            }
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} missing",
+        summary.passed, summary.failed, summary.missing
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_answers() {
+        let answers = Answers::load(Path::new("input/2022/answers.toml")).unwrap();
+        let entry = answers.get(9, 2, "day9.txt").unwrap();
+        assert_eq!(entry.expected, Some(Recorded::Int(2607)));
+        assert!(answers.get(9, 2, "day9-test.txt").is_some());
+        assert!(answers.get(12, 1, "day12.txt").is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        let entry: Entry = toml::from_str(
+            "day = 9\npart = 2\ninput = \"day9.txt\"\nexpected = 2607\nwrong = [2612]",
+        )
+        .unwrap();
+        assert!(matches!(
+            Status::check(Some(&entry), Answer::Int(2607)),
+            Status::Pass
+        ));
+        assert!(matches!(
+            Status::check(Some(&entry), Answer::Int(2612)),
+            Status::KnownWrong(_)
+        ));
+        assert!(matches!(
+            Status::check(Some(&entry), Answer::Int(2611)),
+            Status::Fail { .. }
+        ));
+        assert!(matches!(
+            Status::check(None, Answer::Int(2607)),
+            Status::Missing(_)
+        ));
+    }
+
+    #[test]
+    fn test_grid_matches() {
+        let recorded = Recorded::Str("#.\n.#\n".to_string());
+        let answer = Answer::Grid(vec!["#.".to_string(), ".#".to_string()]);
+        assert!(recorded.matches(&answer));
+    }
+}
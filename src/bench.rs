@@ -0,0 +1,161 @@
+//! The `bench` command: time the parsing and both parts of every day.
+//!
+//! Run it with `cargo run --release -- bench` to get meaningful numbers. The
+//! summary is also written as JSON, so runs before and after a refactoring can
+//! be compared.
+
+use std::any::Any;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::cli::BenchArgs;
+use crate::error::{Error, Result};
+use crate::runner::{days, solution};
+use crate::solution::DynSolution;
+use crate::util::*;
+
+/// Mean, median and minimum of repeated measurements.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub mean_ns: u128,
+    pub median_ns: u128,
+    pub min_ns: u128,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let total: Duration = samples.iter().sum();
+        Stats {
+            mean_ns: total.as_nanos() / samples.len() as u128,
+            median_ns: samples[samples.len() / 2].as_nanos(),
+            min_ns: samples[0].as_nanos(),
+        }
+    }
+}
+
+/// The timings of one day on one input.
+#[derive(Debug, Serialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub input: String,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// The machine readable summary of a `bench` run.
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub iterations: usize,
+    pub days: Vec<DayBench>,
+}
+
+/// Benchmark the selected days, print the timings and write the summary.
+pub fn bench(args: &BenchArgs) -> Vec<Error> {
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days(year()).to_vec(),
+    };
+    let mut errors = Vec::new();
+    let mut summary = Summary {
+        iterations: args.iterations,
+        days: Vec::new(),
+    };
+    for day in days {
+        let Some(solution) = solution(year(), day) else {
+            errors.push(Error::UnknownDay(day));
+            continue;
+        };
+        let input = match &args.input {
+            Some(input) => input.clone(),
+            None => format!("day{}.txt", day),
+        };
+        match bench_day(day, solution, &input, args.iterations) {
+            Ok(result) => {
+                print_day(&result);
+                summary.days.push(result);
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+    if let Err(err) = write_summary(&summary, &args.output) {
+        errors.push(err);
+    }
+    errors
+}
+
+/// Time parsing and both parts separately, `iterations` times each.
+fn bench_day(
+    day: u8,
+    solution: &dyn DynSolution,
+    input: &str,
+    iterations: usize,
+) -> Result<DayBench> {
+    let text = read_file(input)?;
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let (parsed, elapsed) = time(|| solution.parse(&text));
+        let parsed: Box<dyn Any> = parsed.map_err(|err| err.in_file(input))?;
+        parse.push(elapsed);
+        part1.push(time(|| solution.part1(&*parsed)).1);
+        part2.push(time(|| solution.part2(&*parsed)).1);
+    }
+    Ok(DayBench {
+        day,
+        input: input.to_string(),
+        parse: Stats::new(parse),
+        part1: Stats::new(part1),
+        part2: Stats::new(part2),
+    })
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn print_day(result: &DayBench) {
+    let format = |stats: &Stats| {
+        format!(
+            "{:?} / {:?} / {:?}",
+            Duration::from_nanos(stats.mean_ns as u64),
+            Duration::from_nanos(stats.median_ns as u64),
+            Duration::from_nanos(stats.min_ns as u64),
+        )
+    };
+    println!(
+        "Day {} ({}), mean / median / min:",
+        result.day, result.input
+    );
+    println!("  parse:  {}", format(&result.parse));
+    println!("  part 1: {}", format(&result.part1));
+    println!("  part 2: {}", format(&result.part2));
+}
+
+fn write_summary(summary: &Summary, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(summary).expect("Summary is always valid JSON");
+    std::fs::write(path, json + "\n").map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2, 10].map(Duration::from_nanos).to_vec();
+        let stats = Stats::new(samples);
+        assert_eq!(stats.mean_ns, 4);
+        assert_eq!(stats.median_ns, 3);
+        assert_eq!(stats.min_ns, 1);
+    }
+}
@@ -0,0 +1,222 @@
+//! Command line interface of the `aoc2022` binary.
+//!
+//! Without a subcommand every day is run, just like `aoc2022 run --all`.
+//! Inputs are read from the directory of the year, see [`crate::util::year_dir`].
+
+use std::path::PathBuf;
+
+use clap::builder::RangedU64ValueParser;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc2022", about = "Advent of Code 2022 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Directory with a directory of puzzle inputs for each year, like
+    /// `input/2022`. Overrides `AOC2022_INPUT_DIR`.
+    #[arg(long, global = true)]
+    pub input_dir: Option<PathBuf>,
+    /// The year of the puzzles. Defaults to the latest year with solutions.
+    #[arg(long, global = true)]
+    pub year: Option<u16>,
+    /// Show more log output, repeat for more detail. Overrides `AOC2022_LOG`.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the solution for one day or for all days.
+    Run(RunArgs),
+    /// Check the answers of all solutions against the recorded answers.
+    Verify(VerifyArgs),
+    /// Time the parsing and both parts of every day.
+    Bench(BenchArgs),
+    /// Extract the examples from the puzzle descriptions into test fixtures.
+    Examples(ExamplesArgs),
+    /// Create the module and the inputs of a new day.
+    New(NewArgs),
+    /// Copy an input into the input directory and record its checksum.
+    AddInput(AddInputArgs),
+    /// Find inputs that are missing or differ from their recorded checksum.
+    CheckInputs(CheckInputsArgs),
+    /// Run a day again whenever its test or real input changes.
+    Watch(WatchArgs),
+    /// Look for inputs that make a solution panic instead of returning an error.
+    Fuzz(FuzzArgs),
+    /// Print statistics of the calories each elf carries, from the inventory
+    /// of 2022 day 1.
+    Calories(CaloriesArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// The day to run.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub day: Option<u8>,
+    /// Run every day that has a solution.
+    #[arg(long)]
+    pub all: bool,
+    /// Only report this part of the puzzle.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Input file in the input directory, or `-` for stdin. Defaults to both
+    /// the test and the real input of the day.
+    #[arg(long, requires = "day")]
+    pub input: Option<String>,
+    /// How the answers are printed.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+    /// Number of threads that solve days in parallel. Defaults to the number
+    /// of CPUs, use 1 for durations that aren't skewed by other days.
+    #[arg(long, short = 'j', value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub jobs: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One line per answer, meant to be read by humans.
+    Text,
+    /// One JSON object per day, part and input, with the answer, the duration
+    /// and the status from the answers file.
+    Json,
+}
+
+impl RunArgs {
+    /// The arguments used when no subcommand is given.
+    pub fn all() -> RunArgs {
+        RunArgs {
+            day: None,
+            all: true,
+            part: None,
+            input: None,
+            format: Format::Text,
+            jobs: None,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Only verify this day.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+    /// The answers file. Defaults to `answers.toml` in the input directory.
+    #[arg(long)]
+    pub answers: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Only benchmark this day.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+    /// Input file to benchmark with. Defaults to the real input of each day.
+    #[arg(long, requires = "day")]
+    pub input: Option<String>,
+    /// How often each day is run.
+    #[arg(long, short = 'n', default_value_t = 10)]
+    #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub iterations: usize,
+    /// Where the JSON summary is written.
+    #[arg(long, default_value = "target/bench.json")]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct ExamplesArgs {
+    /// Only extract the examples of this day.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// The day to create.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+}
+
+#[derive(Debug, Args)]
+pub struct AddInputArgs {
+    /// The day of the input.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+    /// Store the file as the test input of the day.
+    #[arg(long)]
+    pub test: bool,
+    /// The file to copy, or `-` for stdin.
+    pub file: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckInputsArgs {
+    /// Record the checksums of the current inputs instead of checking them.
+    #[arg(long)]
+    pub record: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// The day to watch.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+    /// How often the inputs are checked for changes, in milliseconds.
+    #[arg(long, default_value_t = 500)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct FuzzArgs {
+    /// Only fuzz the solution of this day.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+    /// How many mutated inputs each solution gets.
+    #[arg(long, short = 'n', default_value_t = 10000)]
+    pub iterations: usize,
+    /// Seed of the random mutations, to repeat an earlier run. Defaults to
+    /// the current time.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// How long a solution may take for one input before it counts as a
+    /// hang, in milliseconds.
+    #[arg(long, default_value_t = 5000)]
+    pub timeout: u64,
+    /// Where the inputs that crash a solution are saved.
+    #[arg(long, default_value = "target/fuzz")]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct CaloriesArgs {
+    /// The inventory in the input directory, or `-` for stdin.
+    #[arg(long, default_value = "day1.txt")]
+    pub input: String,
+    /// How many of the elves carrying the most calories are listed.
+    #[arg(long, default_value_t = 3)]
+    pub top: usize,
+    /// Only print the totals of the top elves, reading the inventory line by
+    /// line. This needs constant memory, even for inventories of many
+    /// gigabytes.
+    #[arg(long)]
+    pub stream: bool,
+    /// The number of histogram buckets.
+    #[arg(long, default_value_t = 10)]
+    #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub buckets: usize,
+    /// How the report is printed.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned columns, meant to be read by humans. With `--stream`, one
+    /// total per line.
+    Table,
+    /// The whole report as one JSON object. With `--stream`, an array of the
+    /// totals.
+    Json,
+}
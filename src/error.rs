@@ -0,0 +1,106 @@
+//! The error type shared by the whole crate.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong while loading and parsing puzzle inputs.
+#[derive(Debug)]
+pub enum Error {
+    /// The input file does not exist.
+    MissingFile(PathBuf),
+    /// The input file exists, but could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The input does not have the format the day expects. Lines and columns
+    /// start at 1.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input has fewer groups of lines than the puzzle needs, like the
+    /// elves of day 1.
+    TooFewGroups { expected: usize, found: usize },
+    /// The input is well formed, but the puzzle has no answer for it, like
+    /// totals that don't fit in a `usize`. There is no position to point at.
+    Unsolvable(String),
+    /// There is no solution for this day (yet).
+    UnknownDay(u8),
+    /// There are no solutions for this year (yet).
+    UnknownYear(u16),
+    /// A new day was requested, but the day already has a solution.
+    DayExists(u8),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A parse error at the given position of an input that is not yet
+    /// associated with a file.
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error::Parse {
+            file: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Attach the name of the input file to a parse error.
+    pub fn in_file(self, filename: &str) -> Error {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => Error::Parse {
+                file: Some(filename.to_string()),
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingFile(path) => write!(f, "Input file {} does not exist", path.display()),
+            Error::Io { path, source } => {
+                write!(f, "Could not read {}: {}", path.display(), source)
+            }
+            Error::Parse {
+                file: Some(file),
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{}:{}: {}", file, line, column, message),
+            Error::Parse {
+                file: None,
+                line,
+                column,
+                message,
+            } => write!(f, "Line {}, column {}: {}", line, column, message),
+            Error::TooFewGroups { expected, found } => {
+                write!(f, "Expected at least {} groups, found {}", expected, found)
+            }
+            Error::Unsolvable(message) => write!(f, "{}", message),
+            Error::UnknownDay(day) => write!(f, "Day {} has no solution yet", day),
+            Error::UnknownYear(year) => write!(f, "There are no solutions for {} yet", year),
+            Error::DayExists(day) => write!(f, "Day {} already has a solution", day),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Extract the worked examples from the puzzle descriptions in the day
+//! modules into test fixtures.
+//!
+//! The descriptions are the comments at the top of each day. The first block
+//! that follows a paragraph like "For example, ...:" is the example input.
+//! The expected answer of a part is the last number in the paragraph before
+//! its question, ignoring anything in parentheses. Lists of the form
+//! `input: ... 23` after a paragraph that mentions examples are extra
+//! examples with a single line of input.
+//!
+//! `aoc2022 examples` writes each example to `examples/dayN-K.txt` in the
+//! input directory, and the expected answers to `examples/answers.toml`. It
+//! warns about days whose description has no examples. The test suite checks
+//! that the fixtures are up to date and that every day solves them.
+
+use std::fs;
+use std::path::PathBuf;
+
+use lazy_regex::regex_is_match;
+use log::warn;
+
+use crate::answers::{Answers, Entry, Recorded};
+use crate::cli::ExamplesArgs;
+use crate::error::{Error, Result};
+use crate::runner::{days, source};
+use crate::util::*;
+
+/// An example input from a puzzle description, with the answers for each
+/// part that the description mentions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub expected: [Option<i64>; 2],
+}
+
+/// The puzzle description in the source of a day: the comment block that
+/// starts with the `--- Day N: ... ---` title, without the comment markers.
+pub fn description(source: &str) -> String {
+    let mut lines = Vec::new();
+    for line in source.lines() {
+        let text = line
+            .strip_prefix("///")
+            .or_else(|| line.strip_prefix("//"))
+            .map(|text| text.strip_prefix(' ').unwrap_or(text));
+        match text {
+            Some(text) if !lines.is_empty() || regex_is_match!(r"Day \d+:", text) => {
+                lines.push(text)
+            }
+            None if line.trim().is_empty() && !lines.is_empty() => lines.push(""),
+            None if !lines.is_empty() => break,
+            _ => {}
+        }
+    }
+    lines.join("\n")
+}
+
+/// Find the examples in a puzzle description. The main example comes first.
+pub fn extract(description: &str) -> Vec<Example> {
+    let paragraphs: Vec<&str> = description
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n'))
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect();
+
+    let mut examples = Vec::new();
+    let intro = paragraphs
+        .iter()
+        .position(|&paragraph| is_prose(paragraph) && introduces_examples(paragraph));
+    if let Some(intro) = intro {
+        let block: Vec<&str> = paragraphs[intro + 1..]
+            .iter()
+            .take_while(|&&paragraph| !is_prose(paragraph))
+            .copied()
+            .collect();
+        if !block.is_empty() {
+            examples.push(Example {
+                input: block.join("\n\n") + "\n",
+                expected: [None, None],
+            });
+        }
+    }
+
+    let sections = paragraphs.split(|&paragraph| paragraph.trim() == "--- Part Two ---");
+    for (part, section) in sections.take(2).enumerate() {
+        if let (Some(main), Some(answer)) = (examples.first_mut(), paragraph_answer(section)) {
+            main.expected[part] = Some(answer);
+        }
+        // Listed answers are more specific, so they win over the paragraph.
+        for (input, answer) in listed_answers(section) {
+            let input = input.to_string() + "\n";
+            match examples.iter_mut().find(|example| example.input == input) {
+                Some(example) => example.expected[part] = Some(answer),
+                None => {
+                    let mut expected = [None, None];
+                    expected[part] = Some(answer);
+                    examples.push(Example { input, expected });
+                }
+            }
+        }
+    }
+    examples
+}
+
+/// Whether a paragraph is text rather than part of an example. List items are
+/// indented by four spaces, example lines are short or not capitalized.
+fn is_prose(paragraph: &str) -> bool {
+    let first = paragraph.lines().next().unwrap_or_default();
+    let capitalized = first.starts_with(|c: char| c.is_ascii_uppercase());
+    first.split_whitespace().count() >= 4 && (capitalized || first.starts_with("    "))
+}
+
+fn introduces_examples(paragraph: &str) -> bool {
+    paragraph.to_lowercase().contains("example") && paragraph.ends_with(':')
+}
+
+/// The answer in the last text paragraph before the question of a part.
+fn paragraph_answer(section: &[&str]) -> Option<i64> {
+    let question = section
+        .iter()
+        .rposition(|paragraph| paragraph.ends_with('?'))?;
+    let paragraph = section[..question].iter().rev().find(|&&paragraph| {
+        is_prose(paragraph) && !paragraph.ends_with(':') && !is_list(paragraph)
+    })?;
+    last_number(paragraph)
+}
+
+/// The `input: ... answer` items of the lists that follow a paragraph about
+/// examples.
+fn listed_answers<'a>(section: &[&'a str]) -> Vec<(&'a str, i64)> {
+    let mut answers = Vec::new();
+    for pair in section.windows(2) {
+        if !introduces_examples(pair[0]) || !is_list(pair[1]) {
+            continue;
+        }
+        for item in pair[1].lines() {
+            let Some((input, text)) = item.trim().split_once(": ") else {
+                continue;
+            };
+            if let (false, Some(answer)) = (input.contains(char::is_whitespace), last_number(text))
+            {
+                answers.push((input, answer));
+            }
+        }
+    }
+    answers
+}
+
+fn is_list(paragraph: &str) -> bool {
+    paragraph.starts_with("    ")
+}
+
+/// The last number in a text, ignoring anything in parentheses.
+fn last_number(text: &str) -> Option<i64> {
+    let mut depth = 0;
+    let mut outside = String::new();
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth == 0 => outside.push(c),
+            _ => {}
+        }
+    }
+    outside
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|word| word.parse().ok())
+        .next_back()
+}
+
+/// The fixture file of an example, relative to the input directory.
+pub fn fixture_name(day: u8, index: usize) -> String {
+    format!("examples/day{}-{}.txt", day, index + 1)
+}
+
+/// The answers file of the fixtures.
+pub fn manifest_path() -> PathBuf {
+    year_dir().join("examples").join("answers.toml")
+}
+
+/// The answers file entries for the examples of a day.
+pub fn entries(day: u8, examples: &[Example]) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for (index, example) in examples.iter().enumerate() {
+        for (part, expected) in (1..).zip(example.expected) {
+            if let Some(expected) = expected {
+                entries.push(Entry {
+                    day,
+                    part,
+                    input: fixture_name(day, index),
+                    expected: Some(Recorded::Int(expected)),
+                    wrong: Vec::new(),
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// Write the fixtures of the selected days and update the answers file.
+pub fn write_fixtures(args: &ExamplesArgs) -> Vec<Error> {
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days(year()).to_vec(),
+    };
+    let mut errors = Vec::new();
+    let path = manifest_path();
+    let mut manifest = match Answers::load(&path) {
+        Ok(manifest) => manifest,
+        Err(err) => return vec![err],
+    };
+    for day in days {
+        let Some(source) = source(year(), day) else {
+            errors.push(Error::UnknownDay(day));
+            continue;
+        };
+        let description = description(source);
+        let examples = extract(&description);
+        // Without examples the fixtures and the fuzzer have nothing from the day.
+        if description.is_empty() {
+            warn!("Day {} has no puzzle description", day);
+        } else if examples.is_empty() {
+            warn!("Day {}: no examples found in the puzzle description", day);
+        }
+        if let Err(err) = write_day(day, &examples) {
+            errors.push(err);
+            continue;
+        }
+        manifest.entries.retain(|entry| entry.day != day);
+        manifest.entries.extend(entries(day, &examples));
+        println!("Day {}: {} examples", day, examples.len());
+    }
+    manifest
+        .entries
+        .sort_by(|a, b| (a.day, &a.input, a.part).cmp(&(b.day, &b.input, b.part)));
+    let text = format!(
+        "# Generated by `aoc2022 examples` from the puzzle descriptions.\n\n{}",
+        toml::to_string(&manifest).expect("Answers are always valid TOML")
+    );
+    if let Err(source) = fs::write(&path, text) {
+        errors.push(Error::Io { path, source });
+    }
+    errors
+}
+
+/// Replace the fixture files of a day.
+fn write_day(day: u8, examples: &[Example]) -> Result<()> {
+    let dir = year_dir().join("examples");
+    let io_error = |path: PathBuf| move |source| Error::Io { path, source };
+    fs::create_dir_all(&dir).map_err(io_error(dir.clone()))?;
+    for entry in fs::read_dir(&dir).map_err(io_error(dir.clone()))? {
+        let path = entry.map_err(io_error(dir.clone()))?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with(&format!("day{}-", day)) && name.ends_with(".txt") {
+            fs::remove_file(&path).map_err(io_error(path.clone()))?;
+        }
+    }
+    for (index, example) in examples.iter().enumerate() {
+        let path = year_dir().join(fixture_name(day, index));
+        fs::write(&path, &example.input).map_err(io_error(path.clone()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::answers::Status;
+    use crate::runner::{solution, solve};
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(&description(source(2022, 1).unwrap()));
+        assert_eq!(examples.len(), 1);
+        assert!(examples[0].input.starts_with("1000\n2000\n3000\n\n4000\n"));
+        assert!(examples[0].input.ends_with("\n\n10000\n"));
+        assert_eq!(examples[0].expected, [Some(24000), Some(45000)]);
+
+        let examples = extract(&description(source(2022, 6).unwrap()));
+        assert_eq!(examples.len(), 5);
+        assert_eq!(examples[0].expected, [Some(7), Some(19)]);
+        assert_eq!(examples[4].input, "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n");
+        assert_eq!(examples[4].expected, [Some(11), Some(26)]);
+    }
+
+    #[test]
+    fn test_days_without_examples() {
+        let without: Vec<u8> = days(2022)
+            .iter()
+            .copied()
+            .filter(|&day| extract(&description(source(2022, day).unwrap())).is_empty())
+            .collect();
+        // Days 5 and 9 stop before the example, day 10 only introduces a
+        // smaller program and day 11 has no description.
+        assert_eq!(without, [5, 9, 10, 11]);
+    }
+
+    #[test]
+    fn test_fixtures() {
+        set_year(2022);
+        let manifest = Answers::load(Path::new("input/2022/examples/answers.toml")).unwrap();
+        for &day in days(2022) {
+            let examples = extract(&description(source(2022, day).unwrap()));
+            for (index, example) in examples.iter().enumerate() {
+                let fixture = read_file(&fixture_name(day, index)).unwrap();
+                assert_eq!(fixture, example.input, "Day {} fixtures are outdated", day);
+            }
+        }
+        for entry in &manifest.entries {
+            let solved = solve(
+                solution(2022, entry.day).unwrap(),
+                &entry.input,
+                Some(entry.part),
+            )
+            .unwrap();
+            let answer = solved.parts[0].answer.clone();
+            let status = Status::check(Some(entry), answer);
+            assert!(
+                matches!(status, Status::Pass),
+                "Day {}, Part {} ({}): {}",
+                entry.day,
+                entry.part,
+                entry.input,
+                status
+            );
+        }
+    }
+}
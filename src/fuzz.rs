@@ -0,0 +1,427 @@
+//! The `fuzz` command: feed every solution mutated copies of its inputs and
+//! report the inputs that make it panic instead of returning an error.
+//!
+//! The seeds are the examples from the puzzle description and the default
+//! inputs of the day. Each run applies a few random edits to a seed, such as
+//! deleting or repeating lines, changing characters or replacing numbers.
+//! Everything runs in process, so no fuzzing toolchain is needed, and the
+//! seed of the random generator is printed so a run can be repeated.
+//!
+//! The parts run as well when the input is small: at most 200 lines, and no
+//! number above 10000. Larger inputs can make a part run for very long, like a
+//! rope that moves billions of steps, so they only go through the parser.
+//! Inputs are solved on a separate thread with a time limit, and one that
+//! takes longer is reported as a hang. Its thread is left behind, since Rust
+//! can't stop it.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cli::FuzzArgs;
+use crate::error::{Error, Result};
+use crate::examples::{description, extract};
+use crate::runner::{days, default_inputs, solution, source};
+use crate::solution::DynSolution;
+use crate::util::*;
+
+/// Characters that are likely to confuse a parser.
+const CHARACTERS: &[char] = &[
+    '0', '1', '9', 'a', 'z', 'A', 'Z', ' ', '\n', '-', '+', '*', ',', ':', '[', ']', '$', '/', '.',
+    'é',
+];
+
+/// The most lines of an input whose parts are run.
+const MAX_PART_LINES: usize = 200;
+
+/// The largest number in an input whose parts are run.
+const MAX_PART_NUMBER: u64 = 10000;
+
+/// Numbers that are likely to be out of range.
+const NUMBERS: &[&str] = &["0", "-1", "1", "4294967296", "99999999999999999999999"];
+
+/// A small xorshift generator, good enough to pick mutations.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Xorshift never leaves zero.
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, `n` must not be zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Apply one to four random edits to an input.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..1 + rng.below(4) {
+        let lines = line_starts(&chars);
+        let line = rng.below(lines.len());
+        let (start, end) = (
+            lines[line],
+            lines.get(line + 1).copied().unwrap_or(chars.len()),
+        );
+        let at = rng.below(chars.len() + 1);
+        match rng.below(7) {
+            0 if at < chars.len() => chars[at] = *rng.pick(CHARACTERS),
+            1 => chars.insert(at, *rng.pick(CHARACTERS)),
+            2 => {
+                let length = rng.below(chars.len() - at + 1).min(8);
+                chars.drain(at..at + length);
+            }
+            3 => chars.truncate(at),
+            4 => {
+                chars.drain(start..end);
+            }
+            5 => {
+                let copy: Vec<char> = chars[start..end].to_vec();
+                chars.splice(start..start, copy);
+            }
+            _ => {
+                // Replace the number at `at`, or insert one.
+                let length = chars[at..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                chars.splice(at..at + length, rng.pick(NUMBERS).chars());
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// The index of the first character of each line.
+fn line_starts(chars: &[char]) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        chars
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == '\n')
+            .map(|(i, _)| i + 1)
+            .filter(|&start| start < chars.len()),
+    );
+    starts
+}
+
+/// The inputs a day's mutations start from. An empty input is always one.
+pub fn seeds(day: u8) -> Vec<String> {
+    let mut seeds = vec![String::new()];
+    if let Some(source) = source(year(), day) {
+        seeds.extend(extract(&description(source)).into_iter().map(|e| e.input));
+    }
+    seeds.extend(default_inputs(day).iter().filter_map(|f| read_file(f).ok()));
+    seeds
+}
+
+thread_local! {
+    /// Whether this thread solves fuzzed inputs, whose panics are reported at
+    /// the end instead of printed.
+    static FUZZING: Cell<bool> = const { Cell::new(false) };
+    /// Where the last panic on this thread happened.
+    static LOCATION: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Install a panic hook that silences and locates the panics of fuzzing
+/// threads, once for the whole process. Other threads, like the tests that
+/// run at the same time, keep the previous hook.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if FUZZING.get() {
+                let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+                LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Whether an input is small enough to run the parts on it.
+fn is_small(input: &str) -> bool {
+    input.lines().count() <= MAX_PART_LINES
+        && input
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .all(|number| number.parse().is_ok_and(|n: u64| n <= MAX_PART_NUMBER))
+}
+
+/// Parse an input and, if it is accepted and small, solve both parts.
+fn solve_input(solution: &dyn DynSolution, input: &str) {
+    if let Ok(parsed) = solution.parse(input) {
+        if is_small(input) {
+            solution.part1(parsed.as_ref());
+            solution.part2(parsed.as_ref());
+        }
+    }
+}
+
+/// An input that made a parser or a part panic or hang.
+#[derive(Debug, Clone)]
+pub struct Crash {
+    pub message: String,
+    /// The file, line and column of the panic, or `hang`.
+    pub location: String,
+    pub input: String,
+}
+
+/// How solving an input went: the message and location of a panic, if any.
+type Outcome = std::result::Result<(), (String, String)>;
+
+/// A thread that solves inputs, so one that hangs can be abandoned.
+struct Worker {
+    inputs: Sender<String>,
+    outcomes: Receiver<Outcome>,
+}
+
+impl Worker {
+    fn spawn(solution: &'static dyn DynSolution) -> Worker {
+        let (inputs, received) = mpsc::channel::<String>();
+        let (sender, outcomes) = mpsc::channel();
+        thread::spawn(move || {
+            FUZZING.set(true);
+            for input in received {
+                let outcome =
+                    panic::catch_unwind(AssertUnwindSafe(|| solve_input(solution, &input)))
+                        .map_err(|payload| {
+                            let location = LOCATION.with(|cell| cell.borrow().clone());
+                            (panic_message(payload), location)
+                        });
+                if sender.send(outcome).is_err() {
+                    break;
+                }
+            }
+        });
+        Worker { inputs, outcomes }
+    }
+
+    /// Solve an input, or `None` if it takes longer than `time_limit`.
+    fn solve(&self, input: String, time_limit: Duration) -> Option<Outcome> {
+        self.inputs.send(input).ok()?;
+        self.outcomes.recv_timeout(time_limit).ok()
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+/// Solve `iterations` mutated seeds, each within `time_limit`. Only the
+/// first crash at each location is reported.
+pub fn fuzz_solution(
+    solution: &'static dyn DynSolution,
+    seeds: &[String],
+    iterations: usize,
+    time_limit: Duration,
+    rng: &mut Rng,
+) -> Vec<Crash> {
+    install_hook();
+    let mut crashes: Vec<Crash> = Vec::new();
+    let mut worker = Worker::spawn(solution);
+    for _ in 0..iterations {
+        let seed = rng.pick(seeds);
+        let input = mutate(rng, seed);
+        let (message, location) = match worker.solve(input.clone(), time_limit) {
+            Some(Ok(())) => continue,
+            Some(Err(panic)) => panic,
+            None => {
+                // The worker is still busy with the input, start a new one.
+                worker = Worker::spawn(solution);
+                let message = format!("No answer after {:?}", time_limit);
+                (message, "hang".to_string())
+            }
+        };
+        if crashes.iter().all(|crash| crash.location != location) {
+            crashes.push(Crash {
+                message,
+                location,
+                input,
+            });
+        }
+    }
+    crashes
+}
+
+/// Fuzz the solutions of the selected days and save the crashing inputs.
+/// Returns whether no solution panicked.
+pub fn fuzz(args: &FuzzArgs) -> Result<bool> {
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days(year()).to_vec(),
+    };
+    let seed = args.seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        now.as_nanos() as u64
+    });
+    println!("Fuzzing with --seed {}", seed);
+    let mut rng = Rng::new(seed);
+    let mut success = true;
+    for day in days {
+        let solution = solution(year(), day).ok_or(Error::UnknownDay(day))?;
+        let crashes = fuzz_solution(
+            solution,
+            &seeds(day),
+            args.iterations,
+            Duration::from_millis(args.timeout),
+            &mut rng,
+        );
+        println!("Day {}: {} crashes", day, crashes.len());
+        for (index, crash) in crashes.iter().enumerate() {
+            let path = save(args.output.clone(), day, index, &crash.input)?;
+            println!(
+                "  {} at {} ({})",
+                crash.message,
+                crash.location,
+                path.display()
+            );
+        }
+        success &= crashes.is_empty();
+    }
+    Ok(success)
+}
+
+fn save(dir: PathBuf, day: u8, index: usize, input: &str) -> Result<PathBuf> {
+    let path = dir.join(format!("day{}-{}.txt", day, index + 1));
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, input))
+        .map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Answer, Solution};
+
+    /// Generous, since the tests run unoptimized and in parallel.
+    const TIME_LIMIT: Duration = Duration::from_secs(10);
+
+    /// A solution whose parser panics on numbers and hangs on anything else.
+    struct Broken;
+
+    impl Solution for Broken {
+        type Input = ();
+
+        fn parse(input: &str) -> Result<()> {
+            if input.contains(|c: char| c.is_ascii_digit()) {
+                panic!("Found a number");
+            }
+            loop {
+                thread::park();
+            }
+        }
+
+        fn part1(_: &()) -> Answer {
+            0.into()
+        }
+
+        fn part2(_: &()) -> Answer {
+            0.into()
+        }
+    }
+
+    #[test]
+    fn test_panics_and_hangs() {
+        let mut rng = Rng::new(2022);
+        let seeds = ["1".to_string(), "a".to_string()];
+        let crashes = fuzz_solution(&Broken, &seeds, 20, Duration::from_millis(50), &mut rng);
+        let mut messages: Vec<&str> = crashes.iter().map(|c| c.message.as_str()).collect();
+        messages.sort();
+        assert_eq!(messages, ["Found a number", "No answer after 50ms"]);
+    }
+
+    #[test]
+    fn test_solutions_do_not_panic() {
+        set_year(2022);
+        let mut rng = Rng::new(2022);
+        for &day in days(2022) {
+            let solution = solution(2022, day).unwrap();
+            let crashes = fuzz_solution(solution, &seeds(day), 300, TIME_LIMIT, &mut rng);
+            assert!(crashes.is_empty(), "Day {}: {:?}", day, crashes);
+        }
+    }
+
+    /// The answers of a 2022 day for an input, or the error of its parser.
+    fn solve(day: u8, input: &str) -> Result<(Answer, Answer)> {
+        let solution = solution(2022, day).unwrap();
+        let parsed = solution.parse(input)?;
+        Ok((
+            solution.part1(parsed.as_ref()),
+            solution.part2(parsed.as_ref()),
+        ))
+    }
+
+    #[test]
+    fn test_former_crashes() {
+        let files = solve(7, "$ cd /\n$ ls\n1 a\n").unwrap();
+        assert_eq!(files, (1.into(), 1.into()));
+
+        let err = solve(10, "addx 2147483647\naddx 1\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 1: X leaves the range -1000000..=1000000"
+        );
+
+        let monkey = |id, items, operation, targets: (u8, u8)| {
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = old {}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n\n",
+                id,
+                items,
+                operation,
+                id + 2,
+                targets.0,
+                targets.1
+            )
+        };
+        let squares =
+            monkey(0, "99999999999999999999999", "* old", (1, 1)) + &monkey(1, "1", "+ 1", (0, 0));
+        assert_eq!(
+            solve(11, &squares).unwrap(),
+            (
+                "Worry levels overflow in the first 20 rounds".into(),
+                399980000.into()
+            )
+        );
+        let throws_to_itself = monkey(0, "1", "+ 1", (0, 1)) + &monkey(1, "1", "+ 1", (0, 0));
+        let err = solve(11, &throws_to_itself).unwrap_err();
+        assert_eq!(err.to_string(), "Line 5, column 30: Can't throw to itself");
+    }
+}
@@ -0,0 +1,305 @@
+//! The input store: the puzzle inputs together with a manifest of their
+//! checksums in `inputs.toml`, so edited or damaged inputs are noticed.
+//!
+//! ```toml
+//! [[input]]
+//! file = "day5.txt"
+//! bytes = 9882
+//! sha256 = "..."
+//! source = "downloads/input.txt"
+//! ```
+//!
+//! `aoc2022 add-input` copies an input into the store and records it,
+//! `aoc2022 check-inputs` compares the inputs to the manifest. Everything
+//! works on local files only.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::cli::{AddInputArgs, CheckInputsArgs};
+use crate::error::{Error, Result};
+use crate::runner::{days, default_inputs};
+use crate::util::*;
+
+/// The contents of the manifest.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Manifest {
+    #[serde(default, rename = "input")]
+    pub entries: Vec<Entry>,
+}
+
+/// What was recorded about one input file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    /// The file name, relative to the input directory.
+    pub file: String,
+    pub bytes: usize,
+    pub sha256: String,
+    /// Where the input was copied from, if it was added with `add-input`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl Entry {
+    pub fn new(file: &str, content: &str, source: Option<String>) -> Entry {
+        Entry {
+            file: file.to_string(),
+            bytes: content.len(),
+            sha256: sha256(content),
+            source,
+        }
+    }
+}
+
+impl Manifest {
+    pub fn default_path() -> PathBuf {
+        year_dir().join("inputs.toml")
+    }
+
+    /// Load the manifest. A manifest that doesn't exist has no entries.
+    pub fn load(path: &Path) -> Result<Manifest> {
+        load_toml(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = format!(
+            "# Checksums of the puzzle inputs, see `aoc2022 check-inputs`.\n\n{}",
+            toml::to_string(self).expect("The manifest is always valid TOML")
+        );
+        fs::write(path, text).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, file: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.file == file)
+    }
+
+    /// Add an entry, replacing the previous entry of the same file.
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.retain(|other| other.file != entry.file);
+        self.entries.push(entry);
+        self.entries
+            .sort_by(|a, b| natural_key(&a.file).cmp(&natural_key(&b.file)));
+    }
+}
+
+/// Sort `day2.txt` before `day10.txt`.
+fn natural_key(file: &str) -> (u32, &str) {
+    let digits: String = file.chars().filter(char::is_ascii_digit).collect();
+    (digits.parse().unwrap_or(0), file)
+}
+
+fn sha256(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// The state of one input file compared to the manifest.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The file is recorded or run by default, but doesn't exist.
+    Missing,
+    /// The file exists, but isn't recorded.
+    Untracked,
+    /// The file is shorter than recorded.
+    Truncated {
+        expected: usize,
+        actual: usize,
+    },
+    /// Only the newlines at the end of the file changed.
+    TrailingNewline,
+    /// The file uses different line endings than recorded.
+    LineEndings,
+    /// The contents of the file changed.
+    Changed,
+}
+
+impl Status {
+    /// Compare the contents of a file to its entry in the manifest.
+    pub fn check(entry: Option<&Entry>, content: Option<&str>) -> Status {
+        let (entry, content) = match (entry, content) {
+            (_, None) => return Status::Missing,
+            (None, Some(_)) => return Status::Untracked,
+            (Some(entry), Some(content)) => (entry, content),
+        };
+        if sha256(content) == entry.sha256 {
+            return Status::Ok;
+        }
+        // The recorded size tells how many newlines the recorded file ended with.
+        let trimmed = content.trim_end_matches(['\n', '\r']);
+        let newlines = entry.bytes.saturating_sub(trimmed.len());
+        for ending in ["\n", "\r\n"] {
+            if newlines % ending.len() == 0 {
+                let candidate = trimmed.to_string() + &ending.repeat(newlines / ending.len());
+                if sha256(&candidate) == entry.sha256 {
+                    return Status::TrailingNewline;
+                }
+            }
+        }
+        let unix = content.replace("\r\n", "\n");
+        let windows = unix.replace('\n', "\r\n");
+        if sha256(&unix) == entry.sha256 || sha256(&windows) == entry.sha256 {
+            return Status::LineEndings;
+        }
+        if content.len() < entry.bytes {
+            return Status::Truncated {
+                expected: entry.bytes,
+                actual: content.len(),
+            };
+        }
+        Status::Changed
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Status::Ok | Status::Untracked)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Missing => write!(f, "MISSING"),
+            Status::Untracked => write!(f, "untracked, record it with --record"),
+            Status::Truncated { expected, actual } => {
+                write!(f, "TRUNCATED, {} of {} bytes", actual, expected)
+            }
+            Status::TrailingNewline => write!(f, "CHANGED, only the newlines at the end"),
+            Status::LineEndings => write!(f, "CHANGED, only the line endings"),
+            Status::Changed => write!(f, "CHANGED"),
+        }
+    }
+}
+
+/// The input files that should be checked: the recorded ones, the default
+/// inputs of every day, and the `.txt` files in the input directory.
+fn files(manifest: &Manifest) -> Vec<String> {
+    let mut files: Vec<String> = manifest
+        .entries
+        .iter()
+        .map(|entry| entry.file.clone())
+        .collect();
+    files.extend(days(year()).iter().flat_map(|&day| default_inputs(day)));
+    if let Ok(dir) = fs::read_dir(year_dir()) {
+        for entry in dir.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.ends_with(".txt") && entry.path().is_file() {
+                files.push(name);
+            }
+        }
+    }
+    files.sort_by(|a, b| natural_key(a).cmp(&natural_key(b)));
+    files.dedup();
+    files
+}
+
+/// Check every input against the manifest, or record the current inputs with
+/// `--record`. Returns whether all inputs are fine.
+pub fn check_inputs(args: &CheckInputsArgs) -> Result<bool> {
+    let path = Manifest::default_path();
+    let mut manifest = Manifest::load(&path)?;
+    let mut success = true;
+    for file in files(&manifest) {
+        let content = match read_file(&file) {
+            Ok(content) => Some(content),
+            Err(Error::MissingFile(_)) => None,
+            Err(err) => return Err(err),
+        };
+        if args.record {
+            if let Some(content) = content {
+                let source = manifest.get(&file).and_then(|entry| entry.source.clone());
+                manifest.insert(Entry::new(&file, &content, source));
+                println!("{}: recorded", file);
+            }
+            continue;
+        }
+        let status = Status::check(manifest.get(&file), content.as_deref());
+        success &= status.is_ok();
+        println!("{}: {}", file, status);
+    }
+    if args.record {
+        manifest.save(&path)?;
+    }
+    Ok(success)
+}
+
+/// Copy an input into the store as the real or test input of a day, and
+/// record its checksum.
+pub fn add_input(args: &AddInputArgs) -> Result<()> {
+    let content = match args.file.as_str() {
+        "-" => read_file("-")?,
+        file => fs::read_to_string(file).map_err(|source| match source.kind() {
+            std::io::ErrorKind::NotFound => Error::MissingFile(PathBuf::from(file)),
+            _ => Error::Io {
+                path: PathBuf::from(file),
+                source,
+            },
+        })?,
+    };
+    let name = match args.test {
+        true => format!("day{}-test.txt", args.day),
+        false => format!("day{}.txt", args.day),
+    };
+    let target = year_dir().join(&name);
+    fs::create_dir_all(year_dir())
+        .and_then(|_| fs::write(&target, &content))
+        .map_err(|source| Error::Io {
+            path: target.clone(),
+            source,
+        })?;
+
+    let path = Manifest::default_path();
+    let mut manifest = Manifest::load(&path)?;
+    let source = match args.file.as_str() {
+        "-" => "stdin".to_string(),
+        file => file.to_string(),
+    };
+    manifest.insert(Entry::new(&name, &content, Some(source)));
+    manifest.save(&path)?;
+    println!("Stored {} ({} bytes)", target.display(), content.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let entry = Entry::new("day5.txt", "    [D]\n[N] [C]\n", None);
+        let check = |content| Status::check(Some(&entry), Some(content));
+        assert_eq!(check("    [D]\n[N] [C]\n"), Status::Ok);
+        assert_eq!(check("    [D]\n[N] [C]"), Status::TrailingNewline);
+        assert_eq!(check("    [D]\n[N] [C]\n\n"), Status::TrailingNewline);
+        assert_eq!(check("    [D]\r\n[N] [C]\r\n"), Status::LineEndings);
+        assert_eq!(
+            check("    [D]\n[N]"),
+            Status::Truncated {
+                expected: 16,
+                actual: 11
+            }
+        );
+        assert_eq!(check("    [D]\n[N] [X]\n"), Status::Changed);
+        assert_eq!(Status::check(Some(&entry), None), Status::Missing);
+        assert_eq!(Status::check(None, Some("")), Status::Untracked);
+    }
+
+    #[test]
+    fn test_inputs_unchanged() {
+        set_year(2022);
+        let manifest = Manifest::load(Path::new("input/2022/inputs.toml")).unwrap();
+        assert!(!manifest.entries.is_empty());
+        for entry in &manifest.entries {
+            let content = read_file(&entry.file).ok();
+            let status = Status::check(Some(entry), content.as_deref());
+            assert_eq!(status, Status::Ok, "{}", entry.file);
+        }
+    }
+}
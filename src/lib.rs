@@ -0,0 +1,22 @@
+//! Advent of Code 2022 solutions, and the tools to run, check and time them.
+//!
+//! Each year is a module like [`y2022`], with a module per day that has a type
+//! implementing [`solution::Solution`]. The [`runner`] looks them up by year
+//! and day, and [`parse`] and [`util`] hold what all years share. The
+//! `aoc2022` binary is a thin command line interface over the command modules.
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod fuzz;
+pub mod inputs;
+pub mod logging;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod util;
+pub mod watch;
+pub mod y2022;
@@ -0,0 +1,33 @@
+//! Leveled logging for the solutions.
+//!
+//! The days log through the [`log`] macros, trace output is hidden unless it
+//! is asked for. The filter is read from `AOC2022_LOG` (e.g. `debug` or
+//! `aoc2022::y2022::day8=trace`) and each `-v` on the command line raises the level
+//! of everything: `-v` is info, `-vv` is debug and `-vvv` is trace.
+
+use env_logger::{Builder, Env};
+use log::LevelFilter;
+
+/// Environment variable with the log filter.
+pub const LOG_VAR: &str = "AOC2022_LOG";
+
+/// Install the logger. Log messages go to stderr so they never mix with the
+/// answers on stdout.
+pub fn init(verbose: u8) {
+    let mut builder = Builder::new();
+    builder.filter_level(LevelFilter::Warn);
+    builder.parse_env(Env::new().filter(LOG_VAR));
+    if verbose > 0 {
+        builder.filter_level(level(verbose));
+    }
+    builder.format_timestamp(None).init();
+}
+
+fn level(verbose: u8) -> LevelFilter {
+    match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
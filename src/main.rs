@@ -1,36 +1,70 @@
-mod datastream;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-// mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod util;
+use std::process::ExitCode;
 
-fn main() {
-    day1::day1();
-    println!("Day 2: {:?}", day2::day2());
-    day3::day3("day3-test.txt");
-    day3::day3("day3.txt");
-    day4::day4("day4-test.txt");
-    day4::day4("day4.txt");
-    day5::day5("day5-test.txt", 3, 3);
-    day5::day5("day5.txt", 9, 8);
-    day6::day6("day6-test.txt");
-    day6::day6("day6.txt");
-    day7::day7("day7-test.txt");
-    day7::day7("day7.txt");
-    //day8::day8("day8-test.txt");
-    //day8::day8("day8.txt");
-    day9::day9("day9-test.txt");
-    day9::day9("day9.txt");
-    day10::day10("day10-test.txt");
-    day10::day10("day10.txt");
-    day11::day11("day11-test.txt");
-    day11::day11("day11.txt");
+use clap::Parser;
+
+use aoc2022::{answers, bench, cli, error, examples, fuzz, inputs, logging};
+use aoc2022::{runner, scaffold, util, watch, y2022};
+
+fn main() -> ExitCode {
+    let cli = cli::Cli::parse();
+    logging::init(cli.verbose);
+    if let Some(dir) = cli.input_dir {
+        util::set_input_dir(dir);
+    }
+    if let Some(year) = cli.year {
+        util::set_year(year);
+    }
+    // Only `new` can start a year without solutions.
+    let creates_year = matches!(cli.command, Some(cli::Command::New(_)));
+    if !creates_year && !runner::YEARS.contains(&util::year()) {
+        report(vec![error::Error::UnknownYear(util::year())]);
+        return ExitCode::FAILURE;
+    }
+    let success = match cli.command {
+        Some(cli::Command::Run(args)) => report(runner::run(&args)),
+        Some(cli::Command::Verify(args)) => match answers::verify(&args) {
+            Ok(summary) => summary.is_success(),
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::Bench(args)) => report(bench::bench(&args)),
+        Some(cli::Command::Examples(args)) => report(examples::write_fixtures(&args)),
+        Some(cli::Command::New(args)) => match scaffold::new_day(&args) {
+            Ok(()) => true,
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::AddInput(args)) => match inputs::add_input(&args) {
+            Ok(()) => true,
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::CheckInputs(args)) => match inputs::check_inputs(&args) {
+            Ok(success) => success,
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::Watch(args)) => match watch::watch(&args) {
+            Ok(()) => true,
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::Fuzz(args)) => match fuzz::fuzz(&args) {
+            Ok(success) => success,
+            Err(err) => report(vec![err]),
+        },
+        Some(cli::Command::Calories(args)) => match y2022::day1::calories(&args) {
+            Ok(()) => true,
+            Err(err) => report(vec![err]),
+        },
+        None => report(runner::run(&cli::RunArgs::all())),
+    };
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Print the errors of a command. Returns whether there were none.
+fn report(errors: Vec<error::Error>) -> bool {
+    for err in &errors {
+        eprintln!("Error: {}", err);
+    }
+    errors.is_empty()
 }
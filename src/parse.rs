@@ -0,0 +1,266 @@
+//! Helpers for parsing puzzle inputs.
+//!
+//! Every piece of the input is a [`Span`], which remembers the line and column
+//! it starts at. Splitting a span keeps track of the positions, so a value
+//! that doesn't parse is reported exactly where it is in the input file.
+
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::util::Grid;
+
+/// A piece of one line of the input. Lines and columns start at 1, columns
+/// count bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// A parse error at the start of the span.
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse(self.line, self.column, message)
+    }
+
+    /// The part of the span starting at the byte `offset`.
+    fn sub(&self, offset: usize, text: &'a str) -> Span<'a> {
+        Span {
+            text,
+            line: self.line,
+            column: self.column + offset,
+        }
+    }
+
+    /// The position of a slice of this span's text.
+    fn slice(&self, text: &'a str) -> Span<'a> {
+        self.sub(text.as_ptr() as usize - self.text.as_ptr() as usize, text)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Parse the whole span, `what` describes the expected value.
+    pub fn parse<T: FromStr>(&self, what: &str) -> Result<T> {
+        self.text
+            .parse()
+            .map_err(|_| self.error(format!("Expected {}, found `{}`", what, self.text)))
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.slice(self.text.trim())
+    }
+
+    /// The whitespace separated words of the span.
+    pub fn words(&self) -> Vec<Span<'a>> {
+        self.text
+            .split_whitespace()
+            .map(|word| self.slice(word))
+            .collect()
+    }
+
+    /// The pieces between the separators.
+    pub fn split(&self, separator: &str) -> Vec<Span<'a>> {
+        self.text
+            .split(separator)
+            .map(|piece| self.slice(piece))
+            .collect()
+    }
+
+    /// The parts before and after the first separator.
+    pub fn split_once(&self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        let (before, after) = self.text.split_once(separator)?;
+        Some((self.slice(before), self.slice(after)))
+    }
+
+    /// Like [`Span::split_once`], but a missing separator is an error.
+    pub fn expect_split(&self, separator: &str) -> Result<(Span<'a>, Span<'a>)> {
+        self.split_once(separator)
+            .ok_or_else(|| self.error(format!("Expected `{}`", separator)))
+    }
+
+    /// The rest of the span after `prefix`, if it starts with it.
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Span<'a>> {
+        let rest = self.text.strip_prefix(prefix)?;
+        Some(self.sub(prefix.len(), rest))
+    }
+
+    /// Like [`Span::strip_prefix`], but a missing prefix is an error.
+    pub fn expect_prefix(&self, prefix: &str) -> Result<Span<'a>> {
+        self.strip_prefix(prefix)
+            .ok_or_else(|| self.error(format!("Expected `{}`", prefix)))
+    }
+
+    /// The character, if the span is exactly one character long.
+    pub fn char(&self) -> Option<char> {
+        let mut chars = self.text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// The characters of the span with their columns.
+    pub fn chars(&self) -> impl Iterator<Item = (usize, char)> + 'a {
+        let column = self.column;
+        self.text
+            .char_indices()
+            .map(move |(offset, c)| (column + offset, c))
+    }
+}
+
+/// The lines of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input.lines().enumerate().map(|(i, text)| Span {
+        text,
+        line: i + 1,
+        column: 1,
+    })
+}
+
+/// The groups of lines that are separated by blank lines. Runs of blank
+/// lines don't make empty groups.
+pub fn blocks(input: &str) -> Vec<Vec<Span<'_>>> {
+    let mut blocks = vec![Vec::new()];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if !blocks.last().unwrap().is_empty() {
+                blocks.push(Vec::new());
+            }
+        } else {
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+    if blocks.last().unwrap().is_empty() {
+        blocks.pop();
+    }
+    blocks
+}
+
+/// One number per line.
+pub fn integers<T: FromStr>(lines: &[Span], what: &str) -> Result<Vec<T>> {
+    lines.iter().map(|line| line.trim().parse(what)).collect()
+}
+
+/// A rectangular grid with one cell per character. `cell` converts the
+/// characters, `what` describes what they must be.
+pub fn grid<T>(input: &str, what: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for line in lines(input) {
+        let row = line
+            .chars()
+            .map(|(column, c)| {
+                cell(c).ok_or_else(|| Error::parse(line.line, column, format!("Expected {}", what)))
+            })
+            .collect::<Result<Vec<T>>>()?;
+        if !rows.is_empty() && row.len() != rows[0].len() {
+            return Err(line.error("All rows must have the same length"));
+        }
+        rows.push(row);
+    }
+    Ok(Grid::from_rows(rows).expect("The rows have the same length"))
+}
+
+/// A grid of single digits.
+pub fn digit_grid(input: &str) -> Result<Grid<u8>> {
+    grid(input, "a digit", |c| {
+        c.to_digit(10).map(|digit| digit as u8)
+    })
+}
+
+/// A grid of any characters.
+pub fn char_grid(input: &str) -> Result<Grid<char>> {
+    grid(input, "a character", Some)
+}
+
+/// Lines of `key: value` pairs, like the description of a monkey.
+#[derive(Debug)]
+pub struct Record<'a> {
+    /// The first line of the record, where missing keys are reported.
+    pub start: Span<'a>,
+    pub fields: Vec<(Span<'a>, Span<'a>)>,
+}
+
+impl<'a> Record<'a> {
+    /// Split each line of a block at the first `: `. Lines without a value,
+    /// like `Monkey 0:`, have an empty value. The block must not be empty,
+    /// which those of [`blocks`] never are.
+    pub fn new(block: &[Span<'a>]) -> Result<Record<'a>> {
+        let start = *block.first().expect("Records have at least one line");
+        let fields = block
+            .iter()
+            .map(|line| match line.text.strip_suffix(':') {
+                Some(key) => Ok((line.slice(key).trim(), line.sub(line.text.len(), ""))),
+                None => {
+                    let (key, value) = line.expect_split(": ")?;
+                    Ok((key.trim(), value.trim()))
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok(Record { start, fields })
+    }
+
+    /// The value of a key.
+    pub fn get(&self, key: &str) -> Result<Span<'a>> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name.text == key)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| self.start.error(format!("Missing `{}`", key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_positions() {
+        let line = lines("\nmove 6 from 2 to 1").nth(1).unwrap();
+        let words = line.words();
+        assert_eq!(words[3].text, "2");
+        assert_eq!((words[3].line, words[3].column), (2, 13));
+        let (_, rest) = line.expect_split(" from ").unwrap();
+        assert_eq!(rest.column, 13);
+        let err = words[0].parse::<usize>("a number").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 2, column 1: Expected a number, found `move`"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks = blocks("1\n2\n\n\n3\n\n");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(integers::<u32>(&blocks[0], "a number").unwrap(), vec![1, 2]);
+        assert_eq!(blocks[1][0].line, 5);
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = digit_grid("12\n34\n").unwrap();
+        assert_eq!(
+            grid.render(|digit| char::from(b'0' + digit)),
+            vec!["12", "34"]
+        );
+        let err = digit_grid("12\n3x\n").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 2: Expected a digit");
+        assert!(char_grid("ab\nc\n").is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let block = &blocks("Monkey 0:\n  Test: divisible by 23\n")[0];
+        let record = Record::new(block).unwrap();
+        let test = record.get("Test").unwrap();
+        assert_eq!(test.column, 9);
+        let divisor = test.expect_prefix("divisible by ").unwrap();
+        assert_eq!(divisor.parse::<u32>("a divisor").unwrap(), 23);
+        assert_eq!(divisor.column, 22);
+        assert!(record.get("Monkey 0").unwrap().is_empty());
+        assert!(record.get("Operation").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Dispatches from years and day numbers to the
+//! [`Solution`](crate::solution::Solution) of each day.
+
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
+
+use crate::answers::{Answers, Status};
+use crate::cli::{Format, RunArgs};
+use crate::error::{Error, Result};
+use crate::solution::{Answer, DynSolution};
+use crate::util::*;
+use crate::y2022;
+
+/// The years that have solutions, oldest first.
+pub const YEARS: [u16; 1] = [2022];
+
+/// The days of a year that have a solution, in the order `run --all` runs
+/// them.
+pub fn days(year: u16) -> &'static [u8] {
+    match year {
+        2022 => &y2022::DAYS,
+        _ => &[],
+    }
+}
+
+/// The solution for a day, if there is one.
+pub fn solution(year: u16, day: u8) -> Option<&'static dyn DynSolution> {
+    match year {
+        2022 => y2022::solution(day),
+        _ => None,
+    }
+}
+
+/// The source code of a day module, with the puzzle description.
+pub fn source(year: u16, day: u8) -> Option<&'static str> {
+    match year {
+        2022 => y2022::source(day),
+        _ => None,
+    }
+}
+
+/// Run the days and inputs selected on the command line. A day that fails
+/// doesn't stop the others, all errors are returned at the end.
+///
+/// The day and input pairs are solved in parallel, but the answers are
+/// printed in the order of the days and inputs.
+pub fn run(args: &RunArgs) -> Vec<Error> {
+    let year = year();
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days(year).to_vec(),
+    };
+    let mut errors = Vec::new();
+    // The JSON records include the status of each answer.
+    let answers = match args.format {
+        Format::Text => Answers::default(),
+        Format::Json => Answers::load(&Answers::default_path()).unwrap_or_else(|err| {
+            errors.push(err);
+            Answers::default()
+        }),
+    };
+    let mut jobs = Vec::new();
+    for day in days {
+        let Some(solution) = solution(year, day) else {
+            errors.push(Error::UnknownDay(day));
+            continue;
+        };
+        let inputs = match &args.input {
+            Some(input) => vec![input.clone()],
+            None => default_inputs(day),
+        };
+        jobs.extend(inputs.into_iter().map(|input| (day, solution, input)));
+    }
+
+    let solve_all = || -> Vec<Result<Solved>> {
+        jobs.par_iter()
+            .map(|(_, solution, input)| solve(*solution, input, args.part))
+            .collect()
+    };
+    let results = match args.jobs {
+        Some(threads) => match ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool.install(solve_all),
+            Err(err) => {
+                warn!(
+                    "Could not start {} threads, using the default: {}",
+                    threads, err
+                );
+                solve_all()
+            }
+        },
+        None => solve_all(),
+    };
+
+    for ((day, _, input), solved) in jobs.iter().zip(results) {
+        match args.format {
+            Format::Text => print_text(*day, input, &solved),
+            Format::Json => print_json(year, *day, input, args.part, &solved, &answers),
+        }
+        if let Err(err) = solved {
+            errors.push(err);
+        }
+    }
+    errors
+}
+
+/// The test input followed by the real input.
+pub fn default_inputs(day: u8) -> Vec<String> {
+    vec![format!("day{}-test.txt", day), format!("day{}.txt", day)]
+}
+
+/// The answers of a day for one input, with the time it took to get them.
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub parts: Vec<SolvedPart>,
+}
+
+#[derive(Debug)]
+pub struct SolvedPart {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// Read and parse an input, then solve the parts selected by `part`, or both
+/// parts if no part is selected.
+pub fn solve(solution: &dyn DynSolution, input: &str, part: Option<u8>) -> Result<Solved> {
+    let text = read_file(input)?;
+    let start = Instant::now();
+    let parsed = solution.parse(&text).map_err(|err| err.in_file(input))?;
+    let parse_time = start.elapsed();
+    debug!("Parsed {} in {:?}", input, parse_time);
+
+    let mut parts = Vec::new();
+    for part in selected_parts(part) {
+        let start = Instant::now();
+        let answer = match part {
+            1 => solution.part1(&*parsed),
+            _ => solution.part2(&*parsed),
+        };
+        let time = start.elapsed();
+        debug!("Solved part {} of {} in {:?}", part, input, time);
+        parts.push(SolvedPart { part, answer, time });
+    }
+    Ok(Solved { parse_time, parts })
+}
+
+/// The selected part, or both parts if no part is selected.
+fn selected_parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// Print the answers as text. Errors are reported by the caller.
+fn print_text(day: u8, input: &str, solved: &Result<Solved>) {
+    let Ok(solved) = solved else {
+        return;
+    };
+    for SolvedPart { part, answer, .. } in &solved.parts {
+        match answer {
+            Answer::Grid(_) => println!("Day {}, Part {} ({}):\n{}", day, part, input, answer),
+            _ => println!("Day {}, Part {} ({}): {}", day, part, input, answer),
+        }
+    }
+}
+
+/// One line of the JSON output.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &'a str,
+    answer: Option<&'a Answer>,
+    parse_ns: Option<u128>,
+    duration_ns: Option<u128>,
+    status: &'static str,
+    error: Option<String>,
+}
+
+/// Print one JSON record per part, with the status from the answers file.
+fn print_json(
+    year: u16,
+    day: u8,
+    input: &str,
+    part: Option<u8>,
+    solved: &Result<Solved>,
+    answers: &Answers,
+) {
+    for record in records(year, day, input, part, solved, answers) {
+        println!(
+            "{}",
+            serde_json::to_string(&record).expect("Records are always valid JSON")
+        );
+    }
+}
+
+/// The JSON records of one day and input. If the input couldn't be solved,
+/// each requested part gets a record with the error.
+fn records<'a>(
+    year: u16,
+    day: u8,
+    input: &'a str,
+    part: Option<u8>,
+    solved: &'a Result<Solved>,
+    answers: &Answers,
+) -> Vec<Record<'a>> {
+    match solved {
+        Ok(solved) => solved
+            .parts
+            .iter()
+            .map(|solved_part| {
+                let entry = answers.get(day, solved_part.part, input);
+                Record {
+                    year,
+                    day,
+                    part: solved_part.part,
+                    input,
+                    answer: Some(&solved_part.answer),
+                    parse_ns: Some(solved.parse_time.as_nanos()),
+                    duration_ns: Some(solved_part.time.as_nanos()),
+                    status: Status::check(entry, solved_part.answer.clone()).name(),
+                    error: None,
+                }
+            })
+            .collect(),
+        Err(err) => selected_parts(part)
+            .into_iter()
+            .map(|part| Record {
+                year,
+                day,
+                part,
+                input,
+                answer: None,
+                parse_ns: None,
+                duration_ns: None,
+                status: "error",
+                error: Some(err.to_string()),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_records() {
+        set_year(2022);
+        let answers = Answers::load(std::path::Path::new("input/2022/answers.toml")).unwrap();
+        let solved = solve(&y2022::day3::Day3, "day3-test.txt", None);
+        let json = records(2022, 3, "day3-test.txt", None, &solved, &answers);
+        let json = serde_json::to_value(json).unwrap();
+        assert_eq!(json[0]["year"], 2022);
+        assert_eq!(json[0]["answer"], 157);
+        assert_eq!(json[0]["status"], "pass");
+        assert_eq!(json[1]["part"], 2);
+
+        let solved = solve(&y2022::day3::Day3, "missing.txt", Some(2));
+        let json = records(2022, 3, "missing.txt", Some(2), &solved, &answers);
+        let json = serde_json::to_value(json).unwrap();
+        assert_eq!(json[0]["status"], "error");
+        assert_eq!(json[0]["answer"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,357 @@
+//! The `new` command, which sets up the module and inputs of a new day.
+//!
+//! The day is registered in the module of its year, like `y2022.rs`: a `mod`
+//! line, `DAYS`, the dispatch and the source lookup. The first day of a year
+//! creates that module and registers the year in `lib.rs` and `runner.rs`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use lazy_regex::regex_captures;
+
+use crate::cli::NewArgs;
+use crate::error::{Error, Result};
+use crate::util::*;
+
+/// The module of a new day. `{year}` and `{day}` are replaced.
+const TEMPLATE: &str = r#"use crate::error::Result;
+use crate::solution::{Answer, Solution};
+
+/// --- Day {day}: ---
+///
+/// Paste the puzzle description here, `aoc2022 examples` reads the examples
+/// from it.
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> Answer {
+        lines.len().into()
+    }
+
+    fn part2(lines: &Vec<String>) -> Answer {
+        lines.len().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_file;
+
+    #[test]
+    fn test_day{day}() {
+        assert_eq!(solve_file::<Day{day}>({year}, "day{day}-test.txt"), (0.into(), 0.into()));
+    }
+}
+"#;
+
+/// The module of a new year with its first day. `{year}` and `{day}` are
+/// replaced.
+const YEAR_TEMPLATE: &str = r#"//! The puzzles of Advent of Code {year}.
+
+pub mod day{day};
+
+use crate::solution::DynSolution;
+
+/// The days that have a solution, in the order `run --all` runs them.
+pub const DAYS: [u8; 1] = [{day}];
+
+/// The solution for a day, if there is one.
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    match day {
+        {day} => Some(&day{day}::Day{day}),
+        _ => None,
+    }
+}
+
+/// The source code of a day module, with the puzzle description.
+pub fn source(day: u8) -> Option<&'static str> {
+    match day {
+        {day} => Some(include_str!("y{year}/day{day}.rs")),
+        _ => None,
+    }
+}
+"#;
+
+/// The directory with the sources of this crate.
+fn src_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+/// Create the module and the empty inputs of a day, and register it.
+pub fn new_day(args: &NewArgs) -> Result<()> {
+    let (year, day) = (year(), args.day);
+    let src = src_dir();
+    let year_module = src.join(format!("y{}.rs", year));
+    let days = src.join(format!("y{}", year));
+    let module = days.join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(Error::DayExists(day));
+    }
+    fs::create_dir_all(&days).map_err(|source| Error::Io {
+        path: days.clone(),
+        source,
+    })?;
+    let text = TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string());
+    write(&module, &text)?;
+    if year_module.exists() {
+        update(&year_module, |text| add_to_year(text, year, day))?;
+    } else {
+        let text = YEAR_TEMPLATE
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string());
+        write(&year_module, &text)?;
+        update(&src.join("lib.rs"), |text| {
+            add_mod(text, &format!("y{}", year))
+        })?;
+        update(&src.join("runner.rs"), |text| add_year(text, year))?;
+    }
+
+    let dir = year_dir();
+    fs::create_dir_all(&dir).map_err(|source| Error::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    for name in [format!("day{}.txt", day), format!("day{}-test.txt", day)] {
+        let path = dir.join(name);
+        if !path.exists() {
+            write(&path, "")?;
+        }
+    }
+    println!("Created {}, rebuild to run it.", module.display());
+    Ok(())
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn update(path: &Path, edit: impl FnOnce(&str) -> String) -> Result<()> {
+    let text = fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    write(path, &edit(&text))
+}
+
+/// Add `pub mod name;` to the block of `mod` declarations, sorted like
+/// rustfmt.
+fn add_mod(text: &str, name: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod "))
+        .unwrap_or(0);
+    let end = start
+        + lines[start..]
+            .iter()
+            .take_while(|line| line.starts_with("pub mod "))
+            .count();
+    let declaration = format!("pub mod {};", name);
+    let mut mods = lines[start..end].to_vec();
+    mods.push(&declaration);
+    // rustfmt sorts by name, without the semicolon.
+    mods.sort_by_key(|line| line.trim_end_matches(';'));
+
+    let mut result: Vec<&str> = lines[..start].to_vec();
+    result.extend(mods);
+    result.extend(&lines[end..]);
+    result.join("\n") + "\n"
+}
+
+/// Add the day to the `mod` declarations, `DAYS`, the dispatch and the source
+/// lookup of its year.
+fn add_to_year(text: &str, year: u16, day: u8) -> String {
+    let text = add_mod(text, &format!("day{}", day));
+    let text = add_to_list(&text, day.into());
+    let text = add_arm(
+        &text,
+        "pub fn solution(",
+        day.into(),
+        &format!("Some(&day{}::Day{})", day, day),
+    );
+    add_arm(
+        &text,
+        "pub fn source(",
+        day.into(),
+        &format!("Some(include_str!(\"y{}/day{}.rs\"))", year, day),
+    )
+}
+
+/// Add the year to the imports, `YEARS` and the dispatch of the runner.
+fn add_year(text: &str, year: u16) -> String {
+    let mut text = text.to_string();
+    if let Some((line, single, several)) =
+        regex_captures!(r"use crate::(?:(y\d+)|\{(y[^}]*)\});", &text)
+    {
+        let module = format!("y{}", year);
+        let mut modules: Vec<&str> = match single {
+            "" => several.split(", ").collect(),
+            single => vec![single],
+        };
+        modules.push(&module);
+        modules.sort();
+        text = text.replace(line, &format!("use crate::{{{}}};", modules.join(", ")));
+    }
+    let text = add_to_list(&text, year);
+    let text = add_arm(&text, "pub fn days(", year, &format!("&y{}::DAYS", year));
+    let text = add_arm(
+        &text,
+        "pub fn solution(",
+        year,
+        &format!("y{}::solution(day)", year),
+    );
+    add_arm(
+        &text,
+        "pub fn source(",
+        year,
+        &format!("y{}::source(day)", year),
+    )
+}
+
+/// Add a number to the `DAYS` or `YEARS` list, keeping it sorted.
+fn add_to_list(text: &str, number: u16) -> String {
+    let Some((line, name, kind, _, numbers)) = regex_captures!(
+        r"pub const (DAYS|YEARS): \[(u8|u16); (\d+)\] = \[([^\]]*)\];",
+        text
+    ) else {
+        return text.to_string();
+    };
+    let mut numbers: Vec<u16> = numbers
+        .split(", ")
+        .filter_map(|number| number.parse().ok())
+        .collect();
+    numbers.push(number);
+    numbers.sort();
+    let list: Vec<String> = numbers.iter().map(u16::to_string).collect();
+    let declaration = format!(
+        "pub const {}: [{}; {}] = [{}];",
+        name,
+        kind,
+        numbers.len(),
+        list.join(", ")
+    );
+    text.replace(line, &declaration)
+}
+
+/// Add the arm `key => value,` to the `match` in the given function, keeping
+/// the arms in order.
+fn add_arm(text: &str, function: &str, key: u16, value: &str) -> String {
+    let Some(start) = text.find(function) else {
+        return text.to_string();
+    };
+    let mut offset = start;
+    for line in text[start..].split_inclusive('\n') {
+        let arm = line.trim_start();
+        let later = match arm.split_once(" => ") {
+            Some(("_", _)) => true,
+            Some((number, _)) => number.parse::<u16>().is_ok_and(|number| number > key),
+            None => false,
+        };
+        if later {
+            let indent = &line[..line.len() - arm.len()];
+            let new_arm = format!("{}{} => {},\n", indent, key, value);
+            return format!("{}{}{}", &text[..offset], new_arm, &text[offset..]);
+        }
+        offset += line.len();
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A year module with days 1 and 3, in the shape `new` writes them.
+    const YEAR: &str = r#"pub mod day1;
+pub mod day3;
+
+pub const DAYS: [u8; 2] = [1, 3];
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    match day {
+        1 => Some(&day1::Day1),
+        3 => Some(&day3::Day3),
+        _ => None,
+    }
+}
+
+pub fn source(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("y2021/day1.rs")),
+        3 => Some(include_str!("y2021/day3.rs")),
+        _ => None,
+    }
+}
+"#;
+
+    /// The runner with one year.
+    const RUNNER: &str = r#"use crate::y2021;
+
+pub const YEARS: [u16; 1] = [2021];
+
+pub fn days(year: u16) -> &'static [u8] {
+    match year {
+        2021 => &y2021::DAYS,
+        _ => &[],
+    }
+}
+
+pub fn solution(year: u16, day: u8) -> Option<&'static dyn DynSolution> {
+    match year {
+        2021 => y2021::solution(day),
+        _ => None,
+    }
+}
+
+pub fn source(year: u16, day: u8) -> Option<&'static str> {
+    match year {
+        2021 => y2021::source(day),
+        _ => None,
+    }
+}
+"#;
+
+    #[test]
+    fn test_register() {
+        let module = add_to_year(YEAR, 2021, 2);
+        assert!(module.starts_with("pub mod day1;\npub mod day2;\npub mod day3;\n"));
+        assert!(module.contains("pub const DAYS: [u8; 3] = [1, 2, 3];"));
+        assert!(module.contains("        2 => Some(&day2::Day2),\n        3 =>"));
+        assert!(
+            module.contains("        2 => Some(include_str!(\"y2021/day2.rs\")),\n        3 =>")
+        );
+        let module = add_to_year(&module, 2021, 25);
+        assert!(module.contains("pub mod day2;\npub mod day25;\npub mod day3;\n"));
+        assert!(module.contains("pub const DAYS: [u8; 4] = [1, 2, 3, 25];"));
+        assert!(module.contains("        25 => Some(&day25::Day25),\n        _ => None,"));
+
+        let lib = add_mod("//! Docs.\n\npub mod cli;\npub mod y2021;\n", "y2022");
+        assert_eq!(
+            lib,
+            "//! Docs.\n\npub mod cli;\npub mod y2021;\npub mod y2022;\n"
+        );
+
+        let runner = add_year(RUNNER, 2022);
+        assert!(runner.starts_with("use crate::{y2021, y2022};\n"));
+        assert!(runner.contains("pub const YEARS: [u16; 2] = [2021, 2022];"));
+        assert!(runner.contains("        2022 => &y2022::DAYS,\n        _ => &[],"));
+        assert!(runner.contains("        2022 => y2022::solution(day),\n        _ => None,"));
+        assert!(runner.contains("        2022 => y2022::source(day),\n        _ => None,"));
+        let runner = add_year(&runner, 2020);
+        assert!(runner.starts_with("use crate::{y2020, y2021, y2022};\n"));
+        assert!(runner.contains("pub const YEARS: [u16; 3] = [2020, 2021, 2022];"));
+        assert!(runner.contains("        2020 => &y2020::DAYS,\n        2021 =>"));
+    }
+}
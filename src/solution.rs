@@ -0,0 +1,127 @@
+//! The common shape of every day: parse the input once, then solve both parts
+//! on the parsed input.
+
+use std::any::Any;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::Result;
+
+/// The answer to one part of a puzzle. In JSON it is a number, a string or
+/// an array of rows. Numbers that don't fit in an `i64` are kept as strings,
+/// so they are never printed wrapped around.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    /// A picture drawn by the puzzle, one string per row.
+    Grid(Vec<String>),
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        i64::try_from(value).map_or_else(|_| Answer::Str(value.to_string()), Answer::Int)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Grid(rows)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Str(value) => write!(f, "{}", value),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// A solution for one day of the advent calendar.
+pub trait Solution {
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// A type erased [`Solution`], so the runner can pick a day at runtime.
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn part1(&self, input: &dyn Any) -> Answer;
+    fn part2(&self, input: &dyn Any) -> Answer;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer {
+        S::part1(input.downcast_ref().expect("Input of a different day"))
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer {
+        S::part2(input.downcast_ref().expect("Input of a different day"))
+    }
+}
+
+/// Solve both parts of a day for an input file of the given year, used by the
+/// tests.
+#[cfg(test)]
+pub fn solve_file<S: Solution>(year: u16, filename: &str) -> (Answer, Answer) {
+    let path = crate::util::input_dir()
+        .join(year.to_string())
+        .join(filename);
+    let input = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Can't read {}: {}", path.display(), err));
+    let input = S::parse(&input).unwrap();
+    (S::part1(&input), S::part2(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_answers() {
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from(u32::MAX), Answer::Int(4294967295));
+        assert_eq!(Answer::from(-1), Answer::Int(-1));
+    }
+}
@@ -1,11 +1,173 @@
 //! Shared utility functions
 
-use std::{fs::File, io::Read};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-/// Read a file to a string.
-pub fn read_file(filename: &str) -> String {
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+use crate::runner::YEARS;
+
+mod grid;
+mod point;
+
+pub use grid::Grid;
+pub use point::{Direction, Point, Vec2};
+
+/// The environment variable that overrides the input directory.
+pub const INPUT_DIR_VAR: &str = "AOC2022_INPUT_DIR";
+
+/// The input directory given on the command line, if any.
+static INPUT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// The year given on the command line, if any.
+static YEAR: OnceLock<u16> = OnceLock::new();
+
+/// Read all inputs from `dir`, ignoring the environment and the defaults.
+/// Only the first call has an effect.
+pub fn set_input_dir(dir: PathBuf) {
+    let _ = INPUT_DIR.set(dir);
+}
+
+/// Run the puzzles of `year`. Only the first call has an effect.
+pub fn set_year(year: u16) {
+    let _ = YEAR.set(year);
+}
+
+/// The year whose puzzles are run, the latest year with solutions unless
+/// [`set_year`] picked another.
+pub fn year() -> u16 {
+    match YEAR.get() {
+        Some(&year) => year,
+        None => *YEARS.last().expect("There is at least one year"),
+    }
+}
+
+/// The directory with the inputs of all years. In order of precedence this
+/// is the directory set by [`set_input_dir`], the `AOC2022_INPUT_DIR`
+/// environment variable, `input/` if it exists in the current directory, and
+/// finally `$XDG_DATA_HOME/aoc2022/input` (`~/.local/share/aoc2022/input`).
+pub fn input_dir() -> PathBuf {
+    if let Some(dir) = INPUT_DIR.get() {
+        return dir.clone();
+    }
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+    let local = PathBuf::from("input");
+    if local.is_dir() {
+        return local;
+    }
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("HOME").unwrap_or_default()).join(".local/share"),
+    };
+    data_home.join("aoc2022").join("input")
+}
+
+/// The directory with the inputs, answers and examples of the selected
+/// [`year`], like `input/2022`.
+pub fn year_dir() -> PathBuf {
+    input_dir().join(year().to_string())
+}
+
+/// The path of an input file, relative names are resolved against the
+/// [`year_dir`]. `-` is stdin.
+fn input_path(filename: &str) -> PathBuf {
+    match filename {
+        "-" => PathBuf::from("<stdin>"),
+        _ => year_dir().join(filename),
+    }
+}
+
+/// Open an input file to read it line by line, without loading all of it.
+/// Names are resolved like in [`read_file`].
+pub fn open_file(filename: &str) -> Result<Box<dyn BufRead>> {
+    if filename == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let path = input_path(filename);
+    let file = File::open(&path).map_err(|source| match source.kind() {
+        ErrorKind::NotFound => Error::MissingFile(path.clone()),
+        _ => Error::Io {
+            path: path.clone(),
+            source,
+        },
+    })?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Read an input file to a string. Relative names are resolved against the
+/// [`year_dir`], and `-` reads from stdin.
+pub fn read_file(filename: &str) -> Result<String> {
     let mut input = String::new();
-    let mut file = File::open(&format!("input/{}", filename)).unwrap();
-    file.read_to_string(&mut input).unwrap();
-    input
+    open_file(filename)?
+        .read_to_string(&mut input)
+        .map_err(|source| Error::Io {
+            path: input_path(filename),
+            source,
+        })?;
+    Ok(input)
+}
+
+/// Load a TOML file like `answers.toml`. A file that doesn't exist gives the
+/// default value, syntax errors point at their line and column.
+pub fn load_toml<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(source) => {
+            return Err(Error::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    toml::from_str(&text).map_err(|err| {
+        let (line, column) = line_column(&text, err.span().map_or(0, |span| span.start));
+        Error::parse(line, column, err.message()).in_file(&path.display().to_string())
+    })
+}
+
+/// Find the 1-based line and column of a byte offset into the input.
+pub fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_missing_file() {
+        assert!(matches!(read_file("day0.txt"), Err(Error::MissingFile(_))));
+    }
+
+    #[test]
+    fn test_load_toml() {
+        use std::collections::HashMap;
+
+        let missing: HashMap<String, i64> = load_toml(Path::new("input/missing.toml")).unwrap();
+        assert!(missing.is_empty());
+        let path = env::temp_dir().join(format!("aoc2022-{}.toml", std::process::id()));
+        std::fs::write(&path, "a = 1\nb = \n").unwrap();
+        let err = load_toml::<HashMap<String, i64>>(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(err, Error::Parse { line: 2, .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_line_column() {
+        let input = "ab\ncd\nef";
+        assert_eq!(line_column(input, 0), (1, 1));
+        assert_eq!(line_column(input, 4), (2, 2));
+        assert_eq!(line_column(input, 6), (3, 1));
+    }
 }
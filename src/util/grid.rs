@@ -0,0 +1,206 @@
+//! A rectangular grid of cells, addressed by [`Point`]s with the origin in
+//! the top left corner. [`parse::grid`](crate::parse::grid) reads one from the
+//! input.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use super::point::{Direction, Point, Vec2};
+
+/// The offsets of the eight surrounding cells, clockwise from up.
+const ADJACENT: [Vec2; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid from its rows, or `None` if they don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, position: Point) -> Option<usize> {
+        let x = usize::try_from(position.x).ok()?;
+        let y = usize::try_from(position.y).ok()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        self.offset(position).is_some()
+    }
+
+    /// The cell at a position, or `None` outside the grid.
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.offset(position).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// The rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, but then there are no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of one column from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The neighbours along the rows and columns that are inside the grid.
+    pub fn neighbours4(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| position + direction.step())
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The neighbours including the diagonals that are inside the grid.
+    pub fn neighbours8(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        ADJACENT
+            .into_iter()
+            .map(move |offset| position + offset)
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The positions seen from `position` when repeatedly taking `step`, up to
+    /// the edge of the grid. The start itself is not included.
+    pub fn ray(&self, position: Point, step: Vec2) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(position + step), move |&position| {
+            Some(position + step)
+        })
+        .take_while(|&position| self.contains(position))
+    }
+
+    /// Draw the grid as one string per row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> Vec<String> {
+        self.rows()
+            .map(|row| row.iter().map(&cell).collect())
+            .collect()
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point) -> &T {
+        match self.offset(position) {
+            Some(offset) => &self.cells[offset],
+            None => panic!(
+                "{} is outside the {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, position: Point) -> &mut T {
+        match self.offset(position) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!(
+                "{} is outside the {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// A grid of characters prints as its text.
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.render(|&c| c) {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.rows().nth(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.neighbours4(Point::ORIGIN).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+        assert_eq!(
+            grid.ray(Point::new(2, 1), Direction::Left.step())
+                .collect::<Vec<_>>(),
+            vec![Point::new(1, 1), Point::new(0, 1)]
+        );
+        assert_eq!(grid.ray(Point::ORIGIN, Direction::Up.step()).count(), 0);
+        let text = grid.render(|&digit| if digit % 2 == 0 { '#' } else { '.' });
+        assert_eq!(text, vec![".#.", "#.#"]);
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let grid = crate::parse::char_grid("ab\ncd\n").unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+}
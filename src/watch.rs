@@ -0,0 +1,88 @@
+//! The `watch` command: re-run one day whenever one of its inputs changes,
+//! and show how the answers differ from the previous run.
+//!
+//! The inputs are polled, so this works on any file system without extra
+//! dependencies. Changes to the source need a rebuild and are not picked up.
+
+use std::collections::HashMap;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::WatchArgs;
+use crate::error::{Error, Result};
+use crate::runner::{default_inputs, solution, solve, SolvedPart};
+use crate::solution::Answer;
+use crate::util::*;
+
+/// Watch the inputs of a day until the process is stopped.
+pub fn watch(args: &WatchArgs) -> Result<()> {
+    let solution = solution(year(), args.day).ok_or(Error::UnknownDay(args.day))?;
+    let inputs = default_inputs(args.day);
+    let interval = Duration::from_millis(args.interval);
+    let mut previous: HashMap<(String, u8), Answer> = HashMap::new();
+    loop {
+        let stamps = modified(&inputs);
+        for input in &inputs {
+            match solve(solution, input, None) {
+                Ok(solved) => {
+                    for SolvedPart { part, answer, .. } in solved.parts {
+                        let key = (input.clone(), part);
+                        let change = describe(previous.get(&key), &answer);
+                        println!("Day {}, Part {} ({}): {}", args.day, part, input, change);
+                        previous.insert(key, answer);
+                    }
+                }
+                Err(err) => println!("Day {} ({}): ERROR, {}", args.day, input, err),
+            }
+        }
+        println!("Watching {} for changes...", inputs.join(", "));
+        while modified(&inputs) == stamps {
+            thread::sleep(interval);
+        }
+        println!();
+    }
+}
+
+/// When the inputs were last modified. Missing files have no time.
+fn modified(inputs: &[String]) -> Vec<Option<SystemTime>> {
+    inputs
+        .iter()
+        .map(|input| {
+            let metadata = fs::metadata(year_dir().join(input));
+            metadata.and_then(|metadata| metadata.modified()).ok()
+        })
+        .collect()
+}
+
+/// The answer, and how it differs from the previous answer. Grids are
+/// followed by the note on their own line.
+fn describe(previous: Option<&Answer>, answer: &Answer) -> String {
+    let separator = match answer {
+        Answer::Grid(_) => "\n",
+        _ => " ",
+    };
+    match previous {
+        None => answer.to_string(),
+        Some(previous) if previous == answer => format!("{}{}(unchanged)", answer, separator),
+        Some(_) if matches!(answer, Answer::Grid(_)) => {
+            format!("{}{}(changed)", answer, separator)
+        }
+        Some(previous) => format!("{}{}(was {})", answer, separator, previous),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let answer = Answer::Int(95437);
+        assert_eq!(describe(None, &answer), "95437");
+        assert_eq!(describe(Some(&answer), &answer), "95437 (unchanged)");
+        assert_eq!(describe(Some(&Answer::Int(1)), &answer), "95437 (was 1)");
+        let grid = Answer::Grid(vec!["#.".to_string()]);
+        assert_eq!(describe(Some(&answer), &grid), "#.\n(changed)");
+    }
+}
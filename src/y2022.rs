@@ -0,0 +1,55 @@
+//! The puzzles of Advent of Code 2022.
+
+pub mod datastream;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::solution::DynSolution;
+
+/// The days that have a solution, in the order `run --all` runs them.
+pub const DAYS: [u8; 11] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+/// The solution for a day, if there is one.
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    match day {
+        1 => Some(&day1::Day1),
+        2 => Some(&day2::Day2),
+        3 => Some(&day3::Day3),
+        4 => Some(&day4::Day4),
+        5 => Some(&day5::Day5),
+        6 => Some(&day6::Day6),
+        7 => Some(&day7::Day7),
+        8 => Some(&day8::Day8),
+        9 => Some(&day9::Day9),
+        10 => Some(&day10::Day10),
+        11 => Some(&day11::Day11),
+        _ => None,
+    }
+}
+
+/// The source code of a day module, with the puzzle description.
+pub fn source(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("y2022/day1.rs")),
+        2 => Some(include_str!("y2022/day2.rs")),
+        3 => Some(include_str!("y2022/day3.rs")),
+        4 => Some(include_str!("y2022/day4.rs")),
+        5 => Some(include_str!("y2022/day5.rs")),
+        6 => Some(include_str!("y2022/day6.rs")),
+        7 => Some(include_str!("y2022/day7.rs")),
+        8 => Some(include_str!("y2022/day8.rs")),
+        9 => Some(include_str!("y2022/day9.rs")),
+        10 => Some(include_str!("y2022/day10.rs")),
+        11 => Some(include_str!("y2022/day11.rs")),
+        _ => None,
+    }
+}
@@ -22,8 +22,12 @@ pub fn input_to_u8s(input: &str) -> Vec<u8> {
 
 /// Returns the start and end of the first start-of-message marker in the
 /// input. The start is the index of the first character of the marker, and
-/// the end is the index AFTER the last character of the marker.
-pub fn find_start_of_message_marker(n: usize, input: &[u8]) -> (usize, usize) {
+/// the end is the index AFTER the last character of the marker. Returns
+/// `None` if no window of `n` characters is free of duplicates.
+pub fn find_start_of_message_marker(n: usize, input: &[u8]) -> Option<(usize, usize)> {
+    if input.len() < n {
+        return None;
+    }
     // Make an array of 26 counters, one for each letter.
     let mut counters = [0; 26];
     let mut duplication_counter = 0;
@@ -40,6 +44,9 @@ pub fn find_start_of_message_marker(n: usize, input: &[u8]) -> (usize, usize) {
     let mut end = n;
     // If we have a start-of-message marker, we're done.
     while duplication_counter > 0 {
+        if end == input.len() {
+            return None;
+        }
         // Update the counters and duplication_counter.
         counters[input[start] as usize] -= 1;
         if counters[input[start] as usize] == 1 {
@@ -54,45 +61,76 @@ pub fn find_start_of_message_marker(n: usize, input: &[u8]) -> (usize, usize) {
         start += 1;
         end += 1;
     }
-    (start, end)
+    Some((start, end))
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+    use proptest::sample::subsequence;
+
     use super::*;
 
+    /// Check every window until one has no duplicates.
+    fn brute_force(n: usize, input: &[u8]) -> Option<(usize, usize)> {
+        input
+            .windows(n)
+            .position(|window| (1..n).all(|i| !window[..i].contains(&window[i])))
+            .map(|start| (start, start + n))
+    }
+
+    /// A marker length and a stream that often ends with a marker of that
+    /// length. The prefix uses few letters, so it has many repeats.
+    fn stream() -> impl Strategy<Value = (usize, Vec<u8>)> {
+        (1..=14usize).prop_flat_map(|n| {
+            let prefix = prop::collection::vec(0..4u8, 0..40);
+            let marker = subsequence((0..26).collect::<Vec<u8>>(), 0..=n).prop_shuffle();
+            (Just(n), prefix, marker).prop_map(|(n, mut prefix, marker)| {
+                prefix.extend(marker);
+                (n, prefix)
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_marker_matches_brute_force((n, input) in stream()) {
+            prop_assert_eq!(find_start_of_message_marker(n, &input), brute_force(n, &input));
+        }
+    }
+
     #[test]
     fn test1() {
         let input = input_to_u8s("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
-        assert_eq!(find_start_of_message_marker(4, &input), (3, 7));
-        assert_eq!(find_start_of_message_marker(14, &input), (5, 19));
+        assert_eq!(find_start_of_message_marker(4, &input), Some((3, 7)));
+        assert_eq!(find_start_of_message_marker(14, &input), Some((5, 19)));
     }
 
     #[test]
     fn test2() {
         let input = input_to_u8s("bvwbjplbgvbhsrlpgdmjqwftvncz");
-        assert_eq!(find_start_of_message_marker(4, &input), (1, 5));
-        assert_eq!(find_start_of_message_marker(14, &input), (9, 23));
+        assert_eq!(find_start_of_message_marker(4, &input), Some((1, 5)));
+        assert_eq!(find_start_of_message_marker(14, &input), Some((9, 23)));
     }
 
     #[test]
     fn test3() {
         let input = input_to_u8s("nppdvjthqldpwncqszvftbrmjlhg");
-        assert_eq!(find_start_of_message_marker(4, &input), (2, 6));
-        assert_eq!(find_start_of_message_marker(14, &input), (9, 23));
+        assert_eq!(find_start_of_message_marker(4, &input), Some((2, 6)));
+        assert_eq!(find_start_of_message_marker(14, &input), Some((9, 23)));
     }
 
     #[test]
     fn test4() {
         let input = input_to_u8s("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
-        assert_eq!(find_start_of_message_marker(4, &input), (6, 10));
-        assert_eq!(find_start_of_message_marker(14, &input), (15, 29));
+        assert_eq!(find_start_of_message_marker(4, &input), Some((6, 10)));
+        assert_eq!(find_start_of_message_marker(14, &input), Some((15, 29)));
     }
 
     #[test]
     fn test5() {
         let input = input_to_u8s("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
-        assert_eq!(find_start_of_message_marker(4, &input), (7, 11));
-        assert_eq!(find_start_of_message_marker(14, &input), (12, 26));
+        assert_eq!(find_start_of_message_marker(4, &input), Some((7, 11)));
+        assert_eq!(find_start_of_message_marker(14, &input), Some((12, 26)));
     }
 }
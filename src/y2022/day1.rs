@@ -0,0 +1,482 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::cli::{CaloriesArgs, ReportFormat};
+use crate::error::{Error, Result};
+use crate::parse::{self, Span};
+use crate::solution::{Answer, Solution};
+use crate::util::{open_file, read_file};
+
+/// --- Day 1: Calorie Counting ---
+///
+/// Santa's reindeer typically eat regular reindeer food, but they need a lot of magical energy to deliver presents on Christmas. For that, their favorite snack is a special type of star fruit that only grows deep in the jungle. The Elves have brought you on their annual expedition to the grove where the fruit grows.
+///
+/// To supply enough magical energy, the expedition needs to retrieve a minimum of fifty stars by December 25th. Although the Elves assure you that the grove has plenty of fruit, you decide to grab any fruit you see along the way, just in case.
+///
+/// Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+///
+/// The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; the Elves' expedition traditionally goes on foot. As your boats approach land, the Elves begin taking inventory of their supplies. One important consideration is food - in particular, the number of Calories each Elf is carrying (your puzzle input).
+///
+/// The Elves take turns writing down the number of Calories contained by the various meals, snacks, rations, etc. that they've brought with them, one item per line. Each Elf separates their own inventory from the previous Elf's inventory (if any) by a blank line.
+///
+/// For example, suppose the Elves finish writing their items' Calories and end up with the following list:
+///
+/// 1000
+/// 2000
+/// 3000
+///
+/// 4000
+///
+/// 5000
+/// 6000
+///
+/// 7000
+/// 8000
+/// 9000
+///
+/// 10000
+///
+/// This list represents the Calories of the food carried by five Elves:
+///
+///     The first Elf is carrying food with 1000, 2000, and 3000 Calories, a total of 6000 Calories.
+///     The second Elf is carrying one food item with 4000 Calories.
+///     The third Elf is carrying food with 5000 and 6000 Calories, a total of 11000 Calories.
+///     The fourth Elf is carrying food with 7000, 8000, and 9000 Calories, a total of 24000 Calories.
+///     The fifth Elf is carrying one food item with 10000 Calories.
+///
+/// In case the Elves get hungry and need extra snacks, they need to know which Elf to ask: they'd like to know how many Calories are being carried by the Elf carrying the most Calories. In the example above, this is 24000 (carried by the fourth Elf).
+///
+/// Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
+///
+/// --- Part Two ---
+///
+/// By the time you calculate the answer to the Elves' question, they've already realized that the Elf carrying the most Calories of food might eventually run out of snacks.
+///
+/// To avoid this unacceptable situation, the Elves would instead like to know the total Calories carried by the top three Elves carrying the most Calories. That way, even if one of those Elves runs out of snacks, they still have two backups.
+///
+/// In the example above, the top three Elves are the fourth Elf (with 24000 Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with 10000 Calories). The sum of the Calories carried by these three elves is 45000.
+///
+/// Find the top three Elves carrying the most Calories. How many Calories are those Elves carrying in total?
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// The answers of both parts from [`solve`], which are checked for
+    /// overflows while parsing.
+    type Input = (usize, usize);
+
+    fn parse(input: &str) -> Result<(usize, usize)> {
+        solve(&parse_inventory(input)?)
+    }
+
+    fn part1(&(most, _): &(usize, usize)) -> Answer {
+        most.into()
+    }
+
+    fn part2(&(_, top_three): &(usize, usize)) -> Answer {
+        top_three.into()
+    }
+}
+
+/// The calories carried by the elf carrying the most, and by the top three
+/// elves together, from the totals of [`parse_inventory`].
+pub fn solve(totals: &[usize]) -> Result<(usize, usize)> {
+    let mut top = TopK::new(3);
+    for &total in totals {
+        top.push_total(total);
+    }
+    let top = top.finish()?;
+    Ok((top[0], top.iter().sum()))
+}
+
+/// Adds up the calories of each elf while the inventory is read line by line.
+/// Blank lines separate the elves, extra ones don't add elves without food.
+#[derive(Debug, Default)]
+struct Totals {
+    /// The total of the elf whose lines are being read.
+    current: Option<usize>,
+}
+
+impl Totals {
+    /// Add the next line of the inventory. Returns the total of an elf when a
+    /// blank line ends it.
+    fn push_line(&mut self, line: Span) -> Result<Option<usize>> {
+        if line.text.trim().is_empty() {
+            return Ok(self.current.take());
+        }
+        let calories: usize = line.trim().parse("a number of calories")?;
+        let total = self.current.unwrap_or(0).checked_add(calories);
+        self.current = Some(total.ok_or_else(|| line.error("Too many calories"))?);
+        Ok(None)
+    }
+
+    /// The total of the last elf, if no blank line ended it.
+    fn finish(&mut self) -> Option<usize> {
+        self.current.take()
+    }
+}
+
+/// The `k` largest calorie totals of an inventory that is read line by line.
+/// Only `k` totals are kept, so the memory doesn't grow with the input.
+pub struct TopK {
+    k: usize,
+    /// The largest totals so far, the smallest of them on top.
+    heap: BinaryHeap<Reverse<usize>>,
+    totals: Totals,
+    elves: usize,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> TopK {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            totals: Totals::default(),
+            elves: 0,
+        }
+    }
+
+    /// Add the next line of the inventory. A blank line ends an elf.
+    pub fn push_line(&mut self, line: Span) -> Result<()> {
+        if let Some(total) = self.totals.push_line(line)? {
+            self.push_total(total);
+        }
+        Ok(())
+    }
+
+    /// Add the total of the next elf.
+    pub fn push_total(&mut self, total: usize) {
+        self.elves += 1;
+        self.heap.push(Reverse(total));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// The `k` largest totals in descending order, or an error if there
+    /// are fewer than `k` elves.
+    pub fn finish(mut self) -> Result<Vec<usize>> {
+        if let Some(total) = self.totals.finish() {
+            self.push_total(total);
+        }
+        if self.elves < self.k {
+            return Err(Error::TooFewGroups {
+                expected: self.k,
+                found: self.elves,
+            });
+        }
+        // Sorting the wrapped totals ascending puts the largest first.
+        let top: Vec<usize> = self
+            .heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(total)| total)
+            .collect();
+        // Part 2 adds them up, which must not overflow either.
+        if top
+            .iter()
+            .try_fold(0usize, |sum, &total| sum.checked_add(total))
+            .is_none()
+        {
+            return Err(Error::Unsolvable(
+                "The top elves carry too many calories".to_string(),
+            ));
+        }
+        Ok(top)
+    }
+}
+
+/// The `k` largest calorie totals of an inventory read from `reader`, in
+/// descending order.
+pub fn top_k(reader: impl BufRead, k: usize) -> Result<Vec<usize>> {
+    let mut top = TopK::new(k);
+    for (index, text) in reader.lines().enumerate() {
+        let text = text.map_err(|source| Error::Io {
+            path: PathBuf::from("<inventory>"),
+            source,
+        })?;
+        top.push_line(Span {
+            text: &text,
+            line: index + 1,
+            column: 1,
+        })?;
+    }
+    top.finish()
+}
+
+/// The calories carried by each elf, in the order of the inventory.
+pub fn parse_inventory(input: &str) -> Result<Vec<usize>> {
+    let mut totals = Totals::default();
+    let mut result = Vec::new();
+    for line in parse::lines(input) {
+        result.extend(totals.push_line(line)?);
+    }
+    result.extend(totals.finish());
+    Ok(result)
+}
+
+/// The statistics of an inventory, printed by the `calories` command.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// The number of elves.
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<Bucket>,
+    /// The elves carrying the most calories, the most first.
+    pub top: Vec<Elf>,
+}
+
+/// The smallest total that at least `percent` percent of the elves don't
+/// exceed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Percentile {
+    pub percent: u8,
+    pub calories: usize,
+}
+
+/// The number of elves carrying `start..=end` calories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub start: usize,
+    /// The largest total in the bucket, so the last one can end at
+    /// `usize::MAX`.
+    pub end: usize,
+    pub elves: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Elf {
+    /// The position of the elf in the inventory, starting at 1.
+    pub elf: usize,
+    pub calories: usize,
+}
+
+/// The percentiles in every report.
+const PERCENTILES: [u8; 5] = [25, 50, 75, 90, 99];
+
+impl Report {
+    /// The statistics of the totals of each elf, with `buckets` equally wide
+    /// histogram buckets and the `k` elves carrying the most. Fewer elves are
+    /// listed if there aren't `k`.
+    pub fn new(totals: &[usize], k: usize, buckets: usize) -> Result<Report> {
+        if totals.is_empty() {
+            return Err(Error::TooFewGroups {
+                expected: 1,
+                found: 0,
+            });
+        }
+        let count = totals.len();
+        let mut sorted = totals.to_vec();
+        sorted.sort_unstable();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+        } else {
+            sorted[count / 2] as f64
+        };
+        // The nearest rank method, so every percentile is an actual total.
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&percent| {
+                let rank = (percent as usize * count).div_ceil(100);
+                Percentile {
+                    percent,
+                    calories: sorted[rank.max(1) - 1],
+                }
+            })
+            .collect();
+
+        // The bounds are worked out in `u128`, so totals near `usize::MAX`
+        // don't overflow them.
+        let (min, max) = (sorted[0] as u128, sorted[count - 1] as u128);
+        let width = (max - min) / buckets.max(1) as u128 + 1;
+        let mut histogram: Vec<Bucket> = (0..buckets.max(1) as u128)
+            .map(|i| min + i * width)
+            .take_while(|&start| start <= max)
+            .map(|start| Bucket {
+                start: start as usize,
+                end: (start + width - 1).min(max) as usize,
+                elves: 0,
+            })
+            .collect();
+        for &total in totals {
+            histogram[((total as u128 - min) / width) as usize].elves += 1;
+        }
+
+        let mut top: Vec<Elf> = totals
+            .iter()
+            .enumerate()
+            .map(|(i, &calories)| Elf {
+                elf: i + 1,
+                calories,
+            })
+            .collect();
+        // Stable, so elves with the same total keep their order.
+        top.sort_by_key(|elf| Reverse(elf.calories));
+        top.truncate(k);
+
+        Ok(Report {
+            count,
+            mean: totals.iter().map(|&total| total as f64).sum::<f64>() / count as f64,
+            median,
+            percentiles,
+            histogram,
+            top,
+        })
+    }
+}
+
+/// The report as a table.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{:>12}", "Elves", self.count)?;
+        writeln!(f, "{:<16}{:>12.1}", "Mean", self.mean)?;
+        writeln!(f, "{:<16}{:>12.1}", "Median", self.median)?;
+        for percentile in &self.percentiles {
+            let name = format!("{}th percentile", percentile.percent);
+            writeln!(f, "{:<16}{:>12}", name, percentile.calories)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<16}{:>12}", "Calories", "Elves")?;
+        for bucket in &self.histogram {
+            let range = format!("{}..={}", bucket.start, bucket.end);
+            writeln!(f, "{:<16}{:>12}", range, bucket.elves)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<16}{:>12}", "Top elves", "Calories")?;
+        for elf in &self.top {
+            writeln!(f, "{:<16}{:>12}", format!("Elf {}", elf.elf), elf.calories)?;
+        }
+        Ok(())
+    }
+}
+
+/// Print the report of an inventory for the `calories` command.
+pub fn calories(args: &CaloriesArgs) -> Result<()> {
+    if args.stream {
+        let top =
+            top_k(open_file(&args.input)?, args.top).map_err(|err| err.in_file(&args.input))?;
+        match args.format {
+            ReportFormat::Table => top.iter().for_each(|total| println!("{}", total)),
+            ReportFormat::Json => println!(
+                "{}",
+                serde_json::to_string(&top).expect("Numbers are always valid JSON")
+            ),
+        }
+        return Ok(());
+    }
+    let input = read_file(&args.input)?;
+    let totals = parse_inventory(&input).map_err(|err| err.in_file(&args.input))?;
+    let report = Report::new(&totals, args.top, args.buckets)?;
+    match args.format {
+        ReportFormat::Table => print!("{}", report),
+        ReportFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Reports are always valid JSON")
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_file;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn test_day1() {
+        assert_eq!(
+            solve_file::<Day1>(2022, "day1-test.txt"),
+            (24000.into(), 45000.into())
+        );
+        assert_eq!(
+            solve_file::<Day1>(2022, "day1.txt"),
+            (69501.into(), 202346.into())
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let totals = parse_inventory(EXAMPLE).unwrap();
+        assert_eq!(solve(&totals).unwrap(), (24000, 45000));
+        let trailing = format!("{}\n\n", EXAMPLE);
+        assert_eq!(parse_inventory(&trailing).unwrap(), totals);
+        let empty = parse_inventory("").unwrap();
+        assert_eq!(empty, Vec::<usize>::new());
+        assert!(matches!(
+            solve(&empty),
+            Err(Error::TooFewGroups {
+                expected: 3,
+                found: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let inventory = "1000\n2000\n\n500\n\n\n4000\n";
+        assert_eq!(top_k(inventory.as_bytes(), 2).unwrap(), vec![4000, 3000]);
+        assert_eq!(top_k(inventory.as_bytes(), 0).unwrap(), Vec::<usize>::new());
+        assert!(matches!(
+            top_k(inventory.as_bytes(), 4),
+            Err(Error::TooFewGroups {
+                expected: 4,
+                found: 3
+            })
+        ));
+        let huge = format!("{}\n\n{}\n", usize::MAX, usize::MAX);
+        assert!(top_k(huge.as_bytes(), 1).is_ok());
+        assert!(matches!(
+            top_k(huge.as_bytes(), 2),
+            Err(Error::Unsolvable(_))
+        ));
+        let err = top_k("1000\nlots\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 2, column 1: Expected a number of calories, found `lots`"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let totals = parse_inventory(EXAMPLE).unwrap();
+        assert_eq!(totals, vec![6000, 4000, 11000, 24000, 10000]);
+        let report = Report::new(&totals, 3, 4).unwrap();
+        assert_eq!(report.count, 5);
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        assert_eq!(
+            report.percentiles[0],
+            Percentile {
+                percent: 25,
+                calories: 6000
+            }
+        );
+        let elves: Vec<usize> = report.histogram.iter().map(|bucket| bucket.elves).collect();
+        assert_eq!(elves, vec![2, 2, 0, 1]);
+        let top: Vec<usize> = report.top.iter().map(|elf| elf.elf).collect();
+        assert_eq!(top, vec![4, 3, 5]);
+        let table = report.to_string();
+        assert!(table
+            .lines()
+            .any(|line| line.split_whitespace().eq(["Elf", "4", "24000"])));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["top"][0]["calories"], 24000);
+        assert!(Report::new(&[], 3, 4).is_err());
+
+        let totals = parse_inventory("0\n\n18446744073709551615\n").unwrap();
+        let report = Report::new(&totals, 3, 10).unwrap();
+        let last = report.histogram.last().unwrap();
+        assert_eq!(last.end, usize::MAX);
+        assert_eq!(last.elves, 1);
+        assert_eq!(report.histogram[0].start, 0);
+        assert_eq!(report.histogram[0].elves, 1);
+    }
+}
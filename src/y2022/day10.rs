@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
-use crate::util::*;
-use lazy_regex::regex_captures;
+use log::{debug, trace};
+
+use crate::error::Result;
+use crate::parse;
+use crate::solution::{Answer, Solution};
+use crate::util::Grid;
 
 // --- Day 10: Cathode-Ray Tube ---
 
@@ -36,45 +40,77 @@ use lazy_regex::regex_captures;
 
 // Maybe you can learn something by looking at the value of the X register throughout execution. For now, consider the signal strength (the cycle number multiplied by the value of the X register) during the 20th cycle and every 40 cycles after that (that is, during the 20th, 60th, 100th, 140th, 180th, and 220th cycles).
 
-#[derive(Debug)]
-enum Instruction {
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
+/// The CPU of the handheld device, running a program one instruction at a
+/// time.
 #[derive(Debug)]
-struct CPU {
-    x: i32,
-    cycle: i32,
-    instr_index: usize,
-    instructions: Vec<Instruction>,
+pub struct Cpu {
+    /// The X register.
+    pub x: i32,
+    /// The number of cycles when the current instruction finished.
+    pub cycle: i32,
+    /// The next instruction to run.
+    pub instr_index: usize,
+    pub instructions: Vec<Instruction>,
 }
 
-pub fn day10(filename: &str) {
-    let input = read_file(filename);
-
-    let mut instructions = Vec::new();
-    for line in input.lines() {
-        if let Some((_, value)) = regex_captures!(r"^addx (-?\d+)$", line) {
-            let instruction = Instruction::Addx(value.parse().unwrap());
-            instructions.push(instruction);
-        } else if let Some(_) = regex_captures!(r"^noop$", line) {
-            let instruction = Instruction::Noop;
-            instructions.push(instruction);
+pub struct Day10;
+
+/// The largest value of X the parser accepts, so the signal strengths can't
+/// overflow. The sprite is off the screen long before that.
+const MAX_X: i64 = 1_000_000;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>> {
+        let mut instructions = Vec::new();
+        let mut x: i64 = 1;
+        for line in parse::lines(input) {
+            if let Some(value) = line.strip_prefix("addx ") {
+                let value = value.parse("a value")?;
+                x += i64::from(value);
+                if x.abs() > MAX_X {
+                    return Err(line.error(format!("X leaves the range -{0}..={0}", MAX_X)));
+                }
+                instructions.push(Instruction::Addx(value));
+            } else if line.text == "noop" {
+                let instruction = Instruction::Noop;
+                instructions.push(instruction);
+            } else {
+                return Err(line.error("Unknown instruction"));
+            }
         }
+        Ok(instructions)
     }
 
-    let mut cpu = CPU {
+    fn part1(instructions: &Vec<Instruction>) -> Answer {
+        signal_strength(instructions).into()
+    }
+
+    fn part2(instructions: &Vec<Instruction>) -> Answer {
+        draw_crt(instructions).into()
+    }
+}
+
+/// Sum the signal strengths during the 20th, 60th, ... 220th cycles.
+fn signal_strength(instructions: &[Instruction]) -> i32 {
+    let mut cpu = Cpu {
         x: 1,
         cycle: 1,
         instr_index: 0,
-        instructions,
+        instructions: instructions.to_vec(),
     };
 
     let mut strength_map: HashMap<usize, i32> = HashMap::new();
     // Simulate the first 20 instructions
     for _ in 0..222 {
         one_step(&mut cpu);
-        // println!("Cycle: {}, X: {}", cpu.cycle, cpu.x);
+        trace!("Cycle: {}, X: {}", cpu.cycle, cpu.x);
 
         let cycle_even = if (cpu.cycle % 2) == 0 {
             cpu.cycle
@@ -83,12 +119,12 @@ pub fn day10(filename: &str) {
         };
 
         if cycle_even == 20 || cycle_even % 40 == 20 {
-            // println!(
-            //     "Cycle: {}, X: {}, Signal strength: {}",
-            //     cycle_even,
-            //     cpu.x,
-            //     cycle_even * cpu.x
-            // );
+            debug!(
+                "Cycle: {}, X: {}, Signal strength: {}",
+                cycle_even,
+                cpu.x,
+                cycle_even * cpu.x
+            );
             strength_map.insert(cycle_even as usize, cycle_even * cpu.x);
         }
     }
@@ -97,28 +133,16 @@ pub fn day10(filename: &str) {
     for (_, value) in strength_map {
         sum += value;
     }
-    println!("Sum: {}", sum);
-
-    println!();
-
-    // Part 2, drawing the sprite
-    // Reset the CPU
-    let mut instructions = Vec::new();
-    for line in input.lines() {
-        if let Some((_, value)) = regex_captures!(r"^addx (-?\d+)$", line) {
-            let instruction = Instruction::Addx(value.parse().unwrap());
-            instructions.push(instruction);
-        } else if let Some(_) = regex_captures!(r"^noop$", line) {
-            let instruction = Instruction::Noop;
-            instructions.push(instruction);
-        }
-    }
+    sum
+}
 
-    let mut cpu = CPU {
+/// Part 2, drawing the sprite
+fn draw_crt(instructions: &[Instruction]) -> Vec<String> {
+    let mut cpu = Cpu {
         x: 1,
         cycle: 0,
         instr_index: 0,
-        instructions,
+        instructions: instructions.to_vec(),
     };
 
     // Execute the program and put all the x values into a vector.
@@ -132,26 +156,24 @@ pub fn day10(filename: &str) {
             cycle += 1;
         }
     }
-    println!("x_values: {:?}", x_values);
-
-    // Print out a 40x6 grid
-    // If the x index is within +/- 1 of the x_values entry, print a #
-    // Otherwise print a .
-    for y in 0..6 {
-        for x in 0..40 {
-            let x_value = x_values[y * 40 + x];
-            let found = x_value >= (x as i32 - 1) && x_value <= (x as i32 + 1);
-            if found {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+    // X keeps its value after the program ends.
+    x_values.resize(240, cpu.x);
+    debug!("x_values: {:?}", x_values);
+
+    // Draw a 40x6 grid
+    // If the x index is within +/- 1 of the x_values entry, draw a #
+    // Otherwise draw a .
+    let mut crt = Grid::new(40, 6, false);
+    for pixel in crt.positions().collect::<Vec<_>>() {
+        let x_value = x_values[(pixel.y * 40 + pixel.x) as usize];
+        let x = pixel.x as i32;
+        crt[pixel] = x_value >= x - 1 && x_value <= x + 1;
     }
+    crt.render(|&lit| if lit { '#' } else { '.' })
 }
 
-fn one_step(cpu: &mut CPU) {
+/// Run the next instruction, if the program hasn't ended.
+pub fn one_step(cpu: &mut Cpu) {
     if cpu.instr_index >= cpu.instructions.len() {
         return;
     }
@@ -0,0 +1,269 @@
+use std::collections::VecDeque;
+
+use log::debug;
+
+use crate::error::{Error, Result};
+use crate::parse::{self, Record, Span};
+use crate::solution::{Answer, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Vec<Monkey>) -> Answer {
+        // Part 1 works with the actual worry levels, which can grow too large
+        // for any input the parser accepts.
+        match monkey_business(monkeys) {
+            Some(business) => business.into(),
+            None => "Worry levels overflow in the first 20 rounds".into(),
+        }
+    }
+
+    fn part2(monkeys: &Vec<Monkey>) -> Answer {
+        worried_monkey_business(monkeys).into()
+    }
+}
+
+/// How the worry level of an item goes down after a monkey inspected it.
+#[derive(Debug, Clone, Copy)]
+enum Relief {
+    /// Divide item value by 3 and round down.
+    DivideByThree,
+    /// No relief, but keep the worry level manageable by working modulo the
+    /// product of all the divisibility tests. This keeps all the tests intact.
+    Modulo(u128),
+}
+
+/// Multiply the inspection counts of the two most active monkeys after 20 rounds.
+/// `None` if a worry level overflows.
+fn monkey_business(monkeys: &[Monkey]) -> Option<usize> {
+    let mut monkeys = monkeys.to_vec();
+    for _ in 0..20 {
+        simulate_all_monkeys(&mut monkeys, Relief::DivideByThree)?;
+    }
+    for monkey in &monkeys {
+        debug!("{:?}", monkey);
+    }
+
+    Some(two_most_active(&monkeys))
+}
+
+/// Multiply the inspection counts of the two most active monkeys after 10000
+/// rounds without relief.
+fn worried_monkey_business(monkeys: &[Monkey]) -> usize {
+    // The parser checks that the modulus fits in 64 bits, so worry levels and
+    // operands below it can't overflow.
+    let modulus: u128 = monkeys.iter().map(|monkey| monkey.test).product();
+    let mut monkeys: Vec<Monkey> = monkeys
+        .iter()
+        .map(|monkey| Monkey {
+            items: monkey.items.iter().map(|item| item % modulus).collect(),
+            operation: monkey.operation.reduce(modulus),
+            ..monkey.clone()
+        })
+        .collect();
+    for _ in 0..10000 {
+        simulate_all_monkeys(&mut monkeys, Relief::Modulo(modulus))
+            .expect("Worry levels below the modulus don't overflow");
+    }
+    two_most_active(&monkeys)
+}
+
+fn two_most_active(monkeys: &[Monkey]) -> usize {
+    let mut counters: Vec<usize> = monkeys.iter().map(|m| m.inspection_counter).collect();
+    counters.sort_by(|a, b| b.cmp(a));
+    counters[0] * counters[1]
+}
+
+fn simulate_all_monkeys(monkeys: &mut [Monkey], relief: Relief) -> Option<()> {
+    for monkey_id in 0..monkeys.len() {
+        simulate_one_monkey(monkeys, monkey_id, relief)?;
+    }
+    Some(())
+}
+
+fn simulate_one_monkey(monkeys: &mut [Monkey], monkey_id: usize, relief: Relief) -> Option<()> {
+    while !monkeys[monkey_id].items.is_empty() {
+        simulate_one_item(monkeys, monkey_id, relief)?;
+    }
+    Some(())
+}
+
+/// Inspect and throw the first item of a monkey, `None` if the worry level
+/// overflows.
+fn simulate_one_item(monkeys: &mut [Monkey], monkey_id: usize, relief: Relief) -> Option<()> {
+    let monkey = &mut monkeys[monkey_id];
+    monkey.inspection_counter += 1;
+    let item = monkey.items.pop_front().unwrap();
+    let new_item = monkey.operation.apply(item)?;
+    let new_item = match relief {
+        Relief::DivideByThree => new_item / 3,
+        Relief::Modulo(modulus) => new_item % modulus,
+    };
+    if new_item % monkey.test == 0 {
+        monkeys[monkey.true_monkey].items.push_back(new_item);
+    } else {
+        monkeys[monkey.false_monkey].items.push_back(new_item);
+    }
+    Some(())
+}
+
+// Monkey definition:
+// Monkey 0:
+//   Starting items: 79, 98
+//   Operation: new = old * 19
+//   Test: divisible by 23
+//     If true: throw to monkey 2
+//     If false: throw to monkey 3
+// Each monkey is a block of `key: value` lines.
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    id: usize,
+    items: VecDeque<u128>,
+    operation: Operation,
+    test: u128,
+    true_monkey: usize,
+    false_monkey: usize,
+    inspection_counter: usize,
+}
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Add(u128),
+    Multiply(u128),
+    Square,
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it overflows.
+    fn apply(self, item: u128) -> Option<u128> {
+        match self {
+            Operation::Add(x) => item.checked_add(x),
+            Operation::Multiply(x) => item.checked_mul(x),
+            Operation::Square => item.checked_mul(item),
+        }
+    }
+
+    /// The same operation on worry levels modulo `modulus`.
+    fn reduce(self, modulus: u128) -> Operation {
+        match self {
+            Operation::Add(x) => Operation::Add(x % modulus),
+            Operation::Multiply(x) => Operation::Multiply(x % modulus),
+            Operation::Square => Operation::Square,
+        }
+    }
+}
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>> {
+    let mut monkeys = Vec::new();
+    let mut spans = Vec::new();
+    for block in parse::blocks(input) {
+        let (monkey, monkey_spans) = parse_monkey(&block)?;
+        // Monkeys throw items by index, so the ids must match the positions.
+        if monkey.id != monkeys.len() {
+            return Err(monkey_spans.start.error("Monkeys must be listed in order"));
+        }
+        monkeys.push(monkey);
+        spans.push(monkey_spans);
+    }
+
+    // Monkey business needs the two most active monkeys.
+    if monkeys.len() < 2 {
+        return Err(Error::TooFewGroups {
+            expected: 2,
+            found: monkeys.len(),
+        });
+    }
+    for (monkey, spans) in monkeys.iter().zip(&spans) {
+        let targets = [
+            (monkey.true_monkey, spans.true_monkey),
+            (monkey.false_monkey, spans.false_monkey),
+        ];
+        for (target, span) in targets {
+            if target >= monkeys.len() {
+                return Err(span.error("Throws to a missing monkey"));
+            }
+        }
+    }
+
+    // Part 2 squares worry levels below the modulus, so it must fit in 64 bits.
+    let mut modulus: u128 = 1;
+    for (monkey, spans) in monkeys.iter().zip(&spans) {
+        modulus = modulus
+            .checked_mul(monkey.test)
+            .filter(|&modulus| modulus <= u64::MAX.into())
+            .ok_or_else(|| spans.test.error("The product of the divisors is too large"))?;
+    }
+
+    Ok(monkeys)
+}
+
+/// Where the fields of a monkey are, for the checks that need all monkeys.
+struct MonkeySpans<'a> {
+    /// The line that starts the monkey.
+    start: Span<'a>,
+    test: Span<'a>,
+    true_monkey: Span<'a>,
+    false_monkey: Span<'a>,
+}
+
+/// Parse one monkey, and return it with the positions of its fields.
+fn parse_monkey<'a>(block: &[Span<'a>]) -> Result<(Monkey, MonkeySpans<'a>)> {
+    let record = Record::new(block)?;
+    let (header, _) = record.fields[0];
+    let id = header.expect_prefix("Monkey ")?.parse("a monkey id")?;
+
+    let items = record
+        .get("Starting items")?
+        .split(",")
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse("a worry level"))
+        .collect::<Result<_>>()?;
+
+    // If the operand is "old", then we use the Square operation.
+    // Otherwise, parse the number and use the Add or Multiply operation.
+    let operation = record.get("Operation")?.expect_prefix("new = old ")?;
+    let (operator, operand) = operation.expect_split(" ")?;
+    let operation = match (operator.text, operand.text) {
+        ("*", "old") => Operation::Square,
+        ("+", _) => Operation::Add(operand.parse("an operand")?),
+        ("*", _) => Operation::Multiply(operand.parse("an operand")?),
+        _ => return Err(operation.error("Expected `+ <number>`, `* <number>` or `* old`")),
+    };
+
+    let test = record.get("Test")?.expect_prefix("divisible by ")?;
+    let true_monkey = record.get("If true")?.expect_prefix("throw to monkey ")?;
+    let false_monkey = record.get("If false")?.expect_prefix("throw to monkey ")?;
+    let monkey = Monkey {
+        id,
+        items,
+        operation,
+        test: test.parse("a divisor")?,
+        true_monkey: true_monkey.parse("a monkey id")?,
+        false_monkey: false_monkey.parse("a monkey id")?,
+        inspection_counter: 0,
+    };
+    if monkey.test == 0 {
+        return Err(test.error("Can't test divisibility by 0"));
+    }
+    // A monkey would keep catching the items it throws to itself.
+    if monkey.true_monkey == id {
+        return Err(true_monkey.error("Can't throw to itself"));
+    }
+    if monkey.false_monkey == id {
+        return Err(false_monkey.error("Can't throw to itself"));
+    }
+    let spans = MonkeySpans {
+        start: record.start,
+        test,
+        true_monkey,
+        false_monkey,
+    };
+    Ok((monkey, spans))
+}
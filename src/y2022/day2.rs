@@ -0,0 +1,427 @@
+use crate::error::Result;
+use crate::parse;
+use crate::solution::{Answer, Solution};
+
+/// --- Day 2: Rock Paper Scissors ---
+///
+/// The Elves begin to set up camp on the beach. To decide whose tent gets to be closest to the snack storage, a giant Rock Paper Scissors tournament is already in progress.
+///
+/// Rock Paper Scissors is a game between two players. Each game contains many rounds; in each round, the players each simultaneously choose one of Rock, Paper, or Scissors using a hand shape. Then, a winner for that round is selected: Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock. If both players choose the same shape, the round instead ends in a draw.
+///
+/// Appreciative of your help yesterday, one Elf gives you an encrypted strategy guide (your puzzle input) that they say will be sure to help you win. "The first column is what your opponent is going to play: A for Rock, B for Paper, and C for Scissors. The second column--" Suddenly, the Elf is called away to help with someone's tent.
+///
+/// The second column, you reason, must be what you should play in response: X for Rock, Y for Paper, and Z for Scissors. Winning every time would be suspicious, so the responses must have been carefully chosen.
+///
+/// The winner of the whole tournament is the player with the highest score. Your total score is the sum of your scores for each round. The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors) plus the score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
+///
+/// Since you can't be sure if the Elf is trying to help you or trick you, you should calculate the score you would get if you were to follow the strategy guide.
+///
+/// For example, suppose you were given the following strategy guide:
+///
+/// A Y
+/// B X
+/// C Z
+///
+/// This strategy guide predicts and recommends the following:
+///
+///     In the first round, your opponent will choose Rock (A), and you should choose Paper (Y). This ends in a win for you with a score of 8 (2 because you chose Paper + 6 because you won).
+///     In the second round, your opponent will choose Paper (B), and you should choose Rock (X). This ends in a loss for you with a score of 1 (1 + 0).
+///     The third round is a draw with both players choosing Scissors, giving you a score of 3 + 3 = 6.
+///
+/// In this example, if you were to follow the strategy guide, you would get a total score of 15 (8 + 1 + 6).
+///
+/// What would your total score be if everything goes exactly according to your strategy guide?
+///
+/// --- Part Two ---
+///
+/// The Elf finishes helping with the tent and sneaks back over to you. "Anyway, the second column says how the round needs to end: X means you need to lose, Y means you need to end the round in a draw, and Z means you need to win. Good luck!"
+///
+/// The total score is still calculated in the same way, but now you need to figure out what shape to choose so the round ends as indicated. The example above now goes like this:
+///
+///     In the first round, your opponent will choose Rock (A), and you need the round to end in a draw (Y), so you also choose Rock. This gives you a score of 1 + 3 = 4.
+///     In the second round, your opponent will choose Paper (B), and you choose Rock so you lose (X) with a score of 1 + 0 = 1.
+///     In the third round, you will defeat your opponent's Scissors with Rock for a score of 1 + 6 = 7.
+///
+/// Now that you're correctly decrypting the ultra top secret strategy guide, you would get a total score of 12.
+///
+/// Following the Elf's instructions for the second column, what would your total score be if everything goes exactly according to your strategy guide?
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = StrategyGuide;
+
+    fn parse(input: &str) -> Result<StrategyGuide> {
+        parse_guide(input, &Symbols::default())
+    }
+
+    fn part1(guide: &StrategyGuide) -> Answer {
+        total_score_part_1(guide).into()
+    }
+
+    fn part2(guide: &StrategyGuide) -> Answer {
+        total_score_part_2(guide).into()
+    }
+}
+
+/// The strategy guide: the opponent's shape and the index of the symbol in
+/// the second column of each round.
+pub struct StrategyGuide {
+    pub game: Game,
+    pub rounds: Vec<(Shape, usize)>,
+}
+
+/// Read a strategy guide whose symbols are given by `symbols`.
+pub fn parse_guide(input: &str, symbols: &Symbols) -> Result<StrategyGuide> {
+    let mut rounds = Vec::new();
+    for line in parse::lines(input) {
+        let (opponent, response) = match line.split_once(" ") {
+            Some((opponent, response)) => (opponent, response),
+            None => return Err(line.error("Expected a round like `A Y`")),
+        };
+        let shape = opponent
+            .char()
+            .and_then(|c| symbols.opponent(c))
+            .ok_or_else(|| opponent.error(format!("Invalid throw `{}`", opponent.text)))?;
+        let index = response
+            .char()
+            .and_then(|c| symbols.response(c))
+            .ok_or_else(|| response.error(format!("Invalid response `{}`", response.text)))?;
+        rounds.push((shape, index));
+    }
+    Ok(StrategyGuide {
+        game: symbols.game(),
+        rounds,
+    })
+}
+
+/// Get the sum of the scores for each round, if the second column is a throw.
+fn total_score_part_1(guide: &StrategyGuide) -> usize {
+    total_score(guide, &Decoding::shapes(guide.game)).expect("The shapes fit every game")
+}
+
+/// Get the sum of the scores for each round, if the second column is a
+/// result.
+fn total_score_part_2(guide: &StrategyGuide) -> usize {
+    total_score(guide, &Decoding::Outcomes).expect("The puzzle is rock, paper, scissors")
+}
+
+/// How the symbols of the second column are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decoding {
+    /// The symbol at position `i` is the shape `shapes[i]`.
+    Shapes(Vec<Shape>),
+    /// The three symbols mean lose, draw and win. Only games with three
+    /// shapes have one symbol per outcome.
+    Outcomes,
+}
+
+impl Decoding {
+    /// The symbols are the shapes in the order of the cycle, like in part 1.
+    pub fn shapes(game: Game) -> Decoding {
+        Decoding::Shapes((0..game.shapes()).map(Shape).collect())
+    }
+
+    /// The symbol at position `i` is the shape at position `order[i]` of the
+    /// cycle, or `None` if `order` is not a permutation of the game's shapes.
+    pub fn permutation(game: Game, order: &[usize]) -> Option<Decoding> {
+        let mut sorted = order.to_vec();
+        sorted.sort_unstable();
+        sorted
+            .into_iter()
+            .eq(0..game.shapes())
+            .then(|| Decoding::Shapes(order.iter().copied().map(Shape).collect()))
+    }
+
+    /// Whether every symbol of the second column of a game can be decoded.
+    pub fn fits(&self, game: Game) -> bool {
+        match self {
+            Decoding::Shapes(shapes) => {
+                shapes.len() == game.shapes() && shapes.iter().all(|shape| shape.0 < game.shapes())
+            }
+            Decoding::Outcomes => game.shapes() == Outcome::ALL.len(),
+        }
+    }
+
+    /// The shape you throw for a symbol of the second column.
+    pub fn decode(&self, game: Game, opponent: Shape, index: usize) -> Shape {
+        match self {
+            Decoding::Shapes(shapes) => shapes[index],
+            Decoding::Outcomes => game.response(opponent, Outcome::ALL[index]),
+        }
+    }
+}
+
+/// The sum of the scores of each round when the second column is read with
+/// `decoding`, or `None` if the decoding doesn't fit the game of the guide.
+pub fn total_score(guide: &StrategyGuide, decoding: &Decoding) -> Option<usize> {
+    let game = guide.game;
+    decoding.fits(game).then(|| {
+        guide
+            .rounds
+            .iter()
+            .map(|&(opponent, index)| game.score(decoding.decode(game, opponent, index), opponent))
+            .sum()
+    })
+}
+
+/// The total score of every way to read the second column as shapes, in
+/// lexicographic order of the permutations.
+pub fn score_permutations(guide: &StrategyGuide) -> Vec<(Decoding, usize)> {
+    let game = guide.game;
+    let shapes = game.shapes();
+    // Only the number of each pair of symbols matters, so count them once
+    // instead of going through the rounds for every permutation.
+    let mut counts = vec![vec![0; shapes]; shapes];
+    for &(opponent, index) in &guide.rounds {
+        counts[opponent.0][index] += 1;
+    }
+    permutations(shapes)
+        .into_iter()
+        .map(|order| {
+            let total = (0..shapes)
+                .flat_map(|opponent| (0..shapes).map(move |index| (opponent, index)))
+                .map(|(opponent, index)| {
+                    counts[opponent][index] * game.score(Shape(order[index]), Shape(opponent))
+                })
+                .sum();
+            let decoding =
+                Decoding::permutation(game, &order).expect("Permutations are permutations");
+            (decoding, total)
+        })
+        .collect()
+}
+
+/// The way to read the second column as shapes that gives the highest total
+/// score, the first in lexicographic order if there are several.
+pub fn best_permutation(guide: &StrategyGuide) -> (Decoding, usize) {
+    score_permutations(guide)
+        .into_iter()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .expect("There is at least one permutation")
+}
+
+/// All orders of `0..n`, in lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            result.push(order);
+        }
+    }
+    result
+}
+
+/// A shape by its position in the cycle of a [`Game`], starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+}
+
+/// A game of shapes arranged in a cycle, where each shape beats the one
+/// before it. With more than three shapes, a shape beats every shape an odd
+/// number of steps before it, so it beats half of the other shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Game {
+    shapes: usize,
+}
+
+impl Game {
+    /// Rock, paper and scissors.
+    pub const ROCK_PAPER_SCISSORS: Game = Game { shapes: 3 };
+    /// Rock, paper, scissors, Spock and lizard, in the order of the cycle.
+    pub const ROCK_PAPER_SCISSORS_SPOCK_LIZARD: Game = Game { shapes: 5 };
+
+    /// A game with `shapes` shapes, which must be odd. With an even number,
+    /// opposite shapes would both beat each other.
+    pub fn new(shapes: usize) -> Option<Game> {
+        (shapes % 2 == 1).then_some(Game { shapes })
+    }
+
+    pub fn shapes(&self) -> usize {
+        self.shapes
+    }
+
+    /// The outcome of a round for you.
+    pub fn outcome(&self, you: Shape, opponent: Shape) -> Outcome {
+        match (you.0 + self.shapes - opponent.0) % self.shapes {
+            0 => Outcome::Draw,
+            steps if steps % 2 == 1 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// The shape to throw for the outcome. To win or lose, this is the
+    /// neighbour of the opponent's shape in the cycle.
+    pub fn response(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        match outcome {
+            Outcome::Loss => Shape((opponent.0 + self.shapes - 1) % self.shapes),
+            Outcome::Draw => opponent,
+            Outcome::Win => Shape((opponent.0 + 1) % self.shapes),
+        }
+    }
+
+    /// Your score for a round: the position of your shape, starting at 1,
+    /// plus 0 for a loss, 3 for a draw and 6 for a win.
+    pub fn score(&self, you: Shape, opponent: Shape) -> usize {
+        let outcome = match self.outcome(you, opponent) {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        };
+        you.0 + 1 + outcome
+    }
+}
+
+/// The symbols of the shapes in each column of a strategy guide, in the
+/// order of the cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    opponent: Vec<char>,
+    response: Vec<char>,
+}
+
+impl Symbols {
+    /// The symbols of both columns, like `ABC` and `XYZ`. Both need one
+    /// different symbol per shape of a [`Game`].
+    pub fn new(opponent: &str, response: &str) -> Option<Symbols> {
+        let symbols = Symbols {
+            opponent: opponent.chars().collect(),
+            response: response.chars().collect(),
+        };
+        let distinct = |column: &[char]| {
+            let mut sorted = column.to_vec();
+            sorted.sort_unstable();
+            sorted.dedup();
+            sorted.len() == column.len()
+        };
+        let shapes = symbols.opponent.len();
+        (symbols.response.len() == shapes
+            && Game::new(shapes).is_some()
+            && distinct(&symbols.opponent)
+            && distinct(&symbols.response))
+        .then_some(symbols)
+    }
+
+    /// The game the symbols are for.
+    pub fn game(&self) -> Game {
+        Game {
+            shapes: self.opponent.len(),
+        }
+    }
+
+    pub fn opponent(&self, c: char) -> Option<Shape> {
+        self.opponent.iter().position(|&s| s == c).map(Shape)
+    }
+
+    /// The position of a symbol of the second column.
+    pub fn response(&self, c: char) -> Option<usize> {
+        self.response.iter().position(|&s| s == c)
+    }
+}
+
+/// `A`, `B` and `C` for the opponent's rock, paper and scissors, and `X`, `Y`
+/// and `Z` in the second column.
+impl Default for Symbols {
+    fn default() -> Symbols {
+        Symbols::new("ABC", "XYZ").expect("Three shapes make a game")
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_file;
+
+    #[test]
+    fn test_day2() {
+        assert_eq!(
+            solve_file::<Day2>(2022, "day2.txt"),
+            (15632.into(), 14416.into())
+        );
+    }
+
+    #[test]
+    fn test_cyclic_games() {
+        let game = Game::ROCK_PAPER_SCISSORS;
+        let (rock, paper, scissors) = (Shape(0), Shape(1), Shape(2));
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(game.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(game.response(scissors, Outcome::Loss), paper);
+        assert_eq!(game.score(rock, scissors), 7);
+
+        // Rock, paper, scissors, Spock and lizard.
+        let game = Game::ROCK_PAPER_SCISSORS_SPOCK_LIZARD;
+        let (spock, lizard) = (Shape(3), Shape(4));
+        assert_eq!(game.outcome(spock, scissors), Outcome::Win);
+        assert_eq!(game.outcome(spock, rock), Outcome::Win);
+        assert_eq!(game.outcome(lizard, spock), Outcome::Win);
+        assert_eq!(game.outcome(lizard, paper), Outcome::Win);
+        assert_eq!(game.outcome(rock, lizard), Outcome::Win);
+        assert_eq!(game.outcome(spock, lizard), Outcome::Loss);
+        for shapes in [3, 5, 7, 9] {
+            let game = Game::new(shapes).unwrap();
+            for you in 0..shapes {
+                let wins = (0..shapes)
+                    .filter(|&other| game.outcome(Shape(you), Shape(other)) == Outcome::Win)
+                    .count();
+                assert_eq!(wins, shapes / 2);
+            }
+        }
+        assert_eq!(Game::new(4), None);
+    }
+
+    #[test]
+    fn test_symbols() {
+        let symbols = Symbols::new("ABCDE", "VWXYZ").unwrap();
+        let guide = parse_guide("D V\nA Y\n", &symbols).unwrap();
+        assert_eq!(guide.game, Game::ROCK_PAPER_SCISSORS_SPOCK_LIZARD);
+        // Rock loses to Spock, and Spock beats rock.
+        assert_eq!(total_score_part_1(&guide), 1 + 4 + 6);
+        assert!(parse_guide("F V\n", &symbols).is_err());
+        assert_eq!(Symbols::new("ABC", "XY"), None);
+        assert_eq!(Symbols::new("AAB", "XYZ"), None);
+        assert_eq!(Symbols::new("ABC", "XYY"), None);
+        let guide = parse_guide("A Z\n", &symbols).unwrap();
+        assert_eq!(total_score(&guide, &Decoding::Outcomes), None);
+    }
+
+    #[test]
+    fn test_decodings() {
+        let guide = Day2::parse("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(total_score(&guide, &Decoding::shapes(guide.game)), Some(15));
+        assert_eq!(total_score(&guide, &Decoding::Outcomes), Some(12));
+        let swapped = Decoding::permutation(guide.game, &[1, 0, 2]).unwrap();
+        // Every round is a draw.
+        assert_eq!(total_score(&guide, &swapped), Some(4 + 5 + 6));
+        assert_eq!(Decoding::permutation(guide.game, &[0, 0, 2]), None);
+        assert_eq!(Decoding::permutation(guide.game, &[0, 1]), None);
+        assert_eq!(Decoding::permutation(guide.game, &[0, 1, 2, 3, 4]), None);
+        let short = Decoding::Shapes(vec![Shape(0), Shape(1)]);
+        assert_eq!(total_score(&guide, &short), None);
+
+        let scores = score_permutations(&guide);
+        assert_eq!(scores.len(), 6);
+        for (decoding, total) in &scores {
+            assert_eq!(total_score(&guide, decoding), Some(*total));
+        }
+        // X is scissors, Y is paper and Z is rock, so every round is won.
+        let best = Decoding::permutation(guide.game, &[2, 1, 0]).unwrap();
+        assert_eq!(best_permutation(&guide), (best, 8 + 9 + 7));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::util::*;
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
 /// --- Day 3: Rucksack Reorganization ---
@@ -63,21 +65,49 @@ use std::collections::HashSet;
 /// Priorities for these items must still be found to organize the sticker attachment efforts: here, they are 18 (r) for the first group and 52 (Z) for the second group. The sum of these is 70.
 ///
 /// Find the item type that corresponds to the badges of each three-Elf group. What is the sum of the priorities of those item types?
-pub fn day3(filename: &str) -> (usize, usize) {
-    let input = read_file(filename);
+pub struct Day3;
 
-    let mut rucksacks = Vec::new();
-    for line in input.lines() {
-        rucksacks.push(Rucksack::new(line));
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Vec<Rucksack>> {
+        let mut rucksacks = Vec::new();
+        for line in parse::lines(input) {
+            if let Some((column, _)) = line.chars().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(Error::parse(line.line, column, "Items must be letters"));
+            }
+            if line.text.len() % 2 != 0 {
+                return Err(line.error("Compartments must be the same size"));
+            }
+            let rucksack = Rucksack::new(line.text);
+            if rucksack.shared_item().is_none() {
+                return Err(line.error("No item is in both compartments"));
+            }
+            rucksacks.push(rucksack);
+        }
+        Ok(rucksacks)
     }
 
-    // Find the score for all shared items
-    let shared_score: u32 = rucksacks.iter().map(|r| item_score(r.shared_item())).sum();
-    println!("Day 3, Part 1: {}", shared_score);
+    fn part1(rucksacks: &Vec<Rucksack>) -> Answer {
+        shared_score(rucksacks).into()
+    }
 
-    // Part 2
-    // First group the rucksacks into groups of 3
+    fn part2(rucksacks: &Vec<Rucksack>) -> Answer {
+        badge_score(rucksacks).into()
+    }
+}
+
+/// Find the score for all shared items
+fn shared_score(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks
+        .iter()
+        .filter_map(|r| r.shared_item())
+        .map(item_score)
+        .sum()
+}
 
+fn badge_score(rucksacks: &[Rucksack]) -> u32 {
+    // First group the rucksacks into groups of 3
     let mut groups = Vec::new();
     for i in 0..rucksacks.len() / 3 {
         let first = &rucksacks[i * 3];
@@ -96,15 +126,13 @@ pub fn day3(filename: &str) -> (usize, usize) {
             }
         }
     }
-    println!("Day 3, Part 2: {}", badge_score);
-
-    (shared_score as usize, badge_score as usize)
+    badge_score
 }
 
 /// Represents a rucksack with two compartments
 /// Each compartment can contain any number of items
 /// If I ever want to make this more efficient, I can use a bitset.
-struct Rucksack {
+pub struct Rucksack {
     first: HashSet<char>,
     second: HashSet<char>,
 }
@@ -117,14 +145,14 @@ impl Rucksack {
             second: second.chars().collect(),
         }
     }
-    /// Finds an item that appears in both compartments. Panics otherwise.
-    fn shared_item(&self) -> char {
+    /// Finds an item that appears in both compartments.
+    fn shared_item(&self) -> Option<char> {
         for c in self.first.iter() {
             if self.second.contains(c) {
-                return *c;
+                return Some(*c);
             }
         }
-        panic!("No shared item found");
+        None
     }
     /// Check if either of the compartments contains the given char
     fn contains(&self, c: char) -> bool {
@@ -148,10 +176,17 @@ fn item_score(c: char) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::solve_file;
 
     #[test]
     fn test_day3() {
-        assert_eq!(day3("day3-test.txt"), (157, 70));
-        assert_eq!(day3("day3.txt"), (8349, 2681));
+        assert_eq!(
+            solve_file::<Day3>(2022, "day3-test.txt"),
+            (157.into(), 70.into())
+        );
+        assert_eq!(
+            solve_file::<Day3>(2022, "day3.txt"),
+            (8349.into(), 2681.into())
+        );
     }
 }
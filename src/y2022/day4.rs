@@ -1,5 +1,6 @@
-use crate::util::*;
-use lazy_regex::regex_captures;
+use crate::error::Result;
+use crate::parse::{self, Span};
+use crate::solution::{Answer, Solution};
 
 // --- Day 4: Camp Cleanup ---
 
@@ -61,7 +62,8 @@ use lazy_regex::regex_captures;
 
 // In how many assignment pairs do the ranges overlap?
 
-struct Range {
+#[derive(Debug)]
+pub struct Range {
     start: u32,
     end: u32,
 }
@@ -79,55 +81,110 @@ impl Range {
     }
 }
 
-fn parse_line(line: &str) -> (Range, Range) {
-    let captures = regex_captures!(r"^(\d+)-(\d+),(\d+)-(\d+)$", line).unwrap();
-    let r1 = Range {
-        start: captures.1.parse().unwrap(),
-        end: captures.2.parse().unwrap(),
-    };
-    let r2 = Range {
-        start: captures.3.parse().unwrap(),
-        end: captures.4.parse().unwrap(),
-    };
-    (r1, r2)
+/// Parse a pair like `2-4,6-8`.
+fn parse_line(line: Span) -> Result<(Range, Range)> {
+    let (first, second) = line.expect_split(",")?;
+    Ok((parse_range(first)?, parse_range(second)?))
 }
 
-pub fn day4(filename: &str) -> (u32, u32) {
-    let input = read_file(filename);
+fn parse_range(range: Span) -> Result<Range> {
+    let (start, end) = range.expect_split("-")?;
+    Ok(Range {
+        start: start.parse("a section id")?,
+        end: end.parse("a section id")?,
+    })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<(Range, Range)>;
 
-    let ranges: Vec<(Range, Range)> = input.lines().map(parse_line).collect();
+    fn parse(input: &str) -> Result<Vec<(Range, Range)>> {
+        parse::lines(input).map(parse_line).collect()
+    }
+
+    fn part1(ranges: &Vec<(Range, Range)>) -> Answer {
+        count_fully_contained(ranges).into()
+    }
 
-    // Check if either range fully contains the other range
+    fn part2(ranges: &Vec<(Range, Range)>) -> Answer {
+        count_overlaps(ranges).into()
+    }
+}
+
+/// Check if either range fully contains the other range
+fn count_fully_contained(ranges: &[(Range, Range)]) -> u32 {
     let mut count_fully_contained = 0;
-    for (r1, r2) in &ranges {
+    for (r1, r2) in ranges {
         if r1.contains(r2) || r2.contains(r1) {
             count_fully_contained += 1;
         }
     }
+    count_fully_contained
+}
 
-    println!("Day 4: {}", count_fully_contained);
-
-    // Part 2: Check if the ranges overlap at all
+/// Part 2: Check if the ranges overlap at all
+fn count_overlaps(ranges: &[(Range, Range)]) -> u32 {
     let mut count_overlaps = 0;
-    for (r1, r2) in &ranges {
+    for (r1, r2) in ranges {
         // If either bound is within the other range, they overlap
         // If a range completely contains the other, they overlap
         if r1.overlaps(r2) {
             count_overlaps += 1;
         }
     }
-    println!("Day 4: {}", count_overlaps);
-
-    (count_fully_contained, count_overlaps)
+    count_overlaps
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::error::Error;
+    use crate::solution::solve_file;
+
+    fn range() -> impl Strategy<Value = Range> {
+        (0..100u32, 0..100u32).prop_map(|(a, b)| Range {
+            start: a.min(b),
+            end: a.max(b),
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_overlaps_is_symmetric(a in range(), b in range()) {
+            prop_assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        }
+
+        #[test]
+        fn test_contains_implies_overlaps(a in range(), b in range()) {
+            prop_assert!(!a.contains(&b) || a.overlaps(&b));
+        }
+    }
 
     #[test]
     fn test_day4() {
-        assert_eq!(day4("day4-test.txt"), (2, 4));
-        assert_eq!(day4("day4.txt"), (560, 839));
+        assert_eq!(
+            solve_file::<Day4>(2022, "day4-test.txt"),
+            (2.into(), 4.into())
+        );
+        assert_eq!(
+            solve_file::<Day4>(2022, "day4.txt"),
+            (560.into(), 839.into())
+        );
+    }
+
+    #[test]
+    fn test_day4_parse_error() {
+        assert!(matches!(
+            Day4::parse("2-4,6-8\n2-3;4-5\n"),
+            Err(Error::Parse {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
     }
 }
@@ -0,0 +1,267 @@
+use crate::error::{Error, Result};
+use crate::parse::{self, Span};
+use crate::solution::{Answer, Solution};
+
+/// --- Day 5: Supply Stacks ---
+///
+/// The expedition can depart as soon as the final supplies have been unloaded from the ships. Supplies are stored in stacks of marked crates, but because the needed supplies are buried under many other crates, the crates need to be rearranged.
+///
+/// The ship has a giant cargo crane capable of moving crates between stacks. To ensure none of the crates get crushed or fall over, the crane operator will rearrange them in a series of carefully-planned steps. After the crates are rearranged, the desired crates will be at the top of each stack.
+///
+/// The Elves don't want to interrupt the crane operator during this delicate procedure, but they forgot to ask her which crate will end up where, and they want to be ready to unload them as soon as possible so they can embark.
+///
+/// They do, however, have a drawing of the starting stacks of crates and the rearrangement procedure (your puzzle input).
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Drawing;
+
+    fn parse(input: &str) -> Result<Drawing> {
+        // The stacks and the instructions are separated by a blank line.
+        let blocks = parse::blocks(input);
+        let (stacks, instructions) = match &blocks[..] {
+            [stacks] => (&stacks[..], &[][..]),
+            [stacks, instructions] => (&stacks[..], &instructions[..]),
+            [] => {
+                return Err(Error::TooFewGroups {
+                    expected: 1,
+                    found: 0,
+                })
+            }
+            [_, _, extra, ..] => {
+                return Err(extra[0].error("Expected only stacks and instructions"))
+            }
+        };
+        let (w, h) = stack_dimensions(stacks)?;
+        let stacks = parse_stacks(&stacks[..h], w);
+        Ok(Drawing {
+            instructions: parse_instructions(instructions, &stacks)?,
+            stacks,
+        })
+    }
+
+    fn part1(drawing: &Drawing) -> Answer {
+        rearrange_ungrouped(drawing).into()
+    }
+
+    fn part2(drawing: &Drawing) -> Answer {
+        rearrange_grouped(drawing).into()
+    }
+}
+
+/// The starting stacks of crates and the rearrangement procedure.
+#[derive(Debug)]
+pub struct Drawing {
+    stacks: Vec<Vec<char>>,
+    instructions: Vec<(usize, usize, usize)>,
+}
+
+/// Part 1: Move the crates one by one
+fn rearrange_ungrouped(drawing: &Drawing) -> String {
+    let mut stacks = drawing.stacks.clone();
+    execute_ungrouped(&drawing.instructions, &mut stacks);
+    read_solution(&stacks)
+}
+
+/// Part 2: Group the stacks
+fn rearrange_grouped(drawing: &Drawing) -> String {
+    let mut stacks = drawing.stacks.clone();
+    execute_grouped(&drawing.instructions, &mut stacks);
+    read_solution(&stacks)
+}
+
+/// Combine the top elements of the stacks into a string, empty stacks are
+/// skipped.
+fn read_solution(stacks: &[Vec<char>]) -> String {
+    let mut result = String::new();
+    for stack in stacks {
+        if let Some(&c) = stack.last() {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Execute the instructions, part 2
+fn execute_grouped(instructions: &[(usize, usize, usize)], stacks: &mut [Vec<char>]) {
+    // Execute the instructions
+    for &(from, to, count) in instructions {
+        // Move the top count elements from the from_stack to the to_stack
+        // Make sure to retain the ordering of the elements
+
+        // First, pop the top count elements from the from_stack
+        let mut intermediate = Vec::new();
+        let mut i = 0;
+        while i < count {
+            let c = stacks[from - 1].pop().unwrap();
+            intermediate.push(c);
+            i += 1;
+        }
+
+        // Now push them onto the to_stack
+        while let Some(c) = intermediate.pop() {
+            stacks[to - 1].push(c);
+        }
+    }
+}
+
+/// Execute the instructions, part 1
+fn execute_ungrouped(instructions: &[(usize, usize, usize)], stacks: &mut [Vec<char>]) {
+    for &(from, to, count) in instructions {
+        // Move the top count elements from the from_stack to the to_stack
+        // This is done one by one and reversing the order of the elements
+        let mut i = 0;
+        while i < count {
+            let c = stacks[from - 1].pop().unwrap();
+            stacks[to - 1].push(c);
+            i += 1;
+        }
+    }
+}
+
+/// Find the number of stacks and the height of the tallest starting stack.
+/// The drawing ends with a line that numbers the stacks, so the last number
+/// on that line is the width and the line index is the height.
+fn stack_dimensions(stacks: &[Span]) -> Result<(usize, usize)> {
+    let numbers = stacks.last().expect("Blocks are never empty");
+    let words = numbers.words();
+    for (i, word) in words.iter().enumerate() {
+        if word.parse::<usize>("a stack number")? != i + 1 {
+            return Err(word.error(format!("Expected stack number {}", i + 1)));
+        }
+    }
+    Ok((words.len(), stacks.len() - 1))
+}
+
+fn parse_stacks(rows: &[Span], w: usize) -> Vec<Vec<char>> {
+    // First parse this input row by row into a vector of vectors of chars
+    //    [D]
+    //[N] [C]
+    //[Z] [M] [P]
+    // 1   2   3
+    // Should be converted to
+    // [['Z', 'N'], ['M', 'C', 'D'], ['P']]
+    let mut stacks: Vec<Vec<char>> = Vec::new();
+    // put w empty stacks in the vector
+    for _ in 0..w {
+        stacks.push(Vec::new());
+    }
+    for row in rows.iter().rev() {
+        for (j, stack) in stacks.iter_mut().enumerate() {
+            // Map 0 -> 1, 2 -> 5, 4 -> 9, 6 -> 13, 8 -> 17
+            // Rows may be missing their trailing spaces.
+            let j2 = 4 * j + 1;
+            let c = row.text.chars().nth(j2).unwrap_or(' ');
+            if c != ' ' {
+                stack.push(c);
+            }
+        }
+    }
+    stacks
+}
+
+fn parse_instructions(lines: &[Span], stacks: &[Vec<char>]) -> Result<Vec<(usize, usize, usize)>> {
+    // Read the instructions. Each instruction is a (usize, usize, usize) pair
+    // Parsing 'move 6 from 2 to 1'.
+    let stack = |number: Span| match number.parse("a stack number")? {
+        stack if (1..=stacks.len()).contains(&stack) => Ok(stack),
+        _ => Err(number.error(format!("There is no stack {}", number.text))),
+    };
+    // Follow the heights of the stacks, so no move takes more crates than
+    // there are.
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    let mut instructions: Vec<(usize, usize, usize)> = Vec::new();
+    for line in lines {
+        let (count, rest) = line.expect_prefix("move ")?.expect_split(" from ")?;
+        let (from, to) = rest.expect_split(" to ")?;
+        let (from, to) = (stack(from)?, stack(to)?);
+        let crates: usize = count.parse("a crate count")?;
+        if crates > heights[from - 1] {
+            return Err(count.error(format!(
+                "Stack {} only has {} crates",
+                from,
+                heights[from - 1]
+            )));
+        }
+        heights[from - 1] -= crates;
+        heights[to - 1] += crates;
+        instructions.push((from, to, crates));
+    }
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::solution::solve_file;
+
+    /// Some stacks of crates and instructions that never take more crates
+    /// from a stack than it holds.
+    fn drawing() -> impl Strategy<Value = Drawing> {
+        let stacks = prop::collection::vec(
+            prop::collection::vec(prop::char::range('A', 'Z'), 0..8),
+            1..10,
+        );
+        let moves = prop::collection::vec((any::<usize>(), any::<usize>(), any::<usize>()), 0..30);
+        (stacks, moves).prop_map(|(stacks, moves)| {
+            let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+            let mut instructions = Vec::new();
+            for (from, to, count) in moves {
+                let (from, to) = (from % stacks.len(), to % stacks.len());
+                let count = count % (heights[from] + 1);
+                heights[from] -= count;
+                heights[to] += count;
+                instructions.push((from + 1, to + 1, count));
+            }
+            Drawing {
+                stacks,
+                instructions,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_crates_are_conserved(drawing in drawing()) {
+            let total: usize = drawing.stacks.iter().map(Vec::len).sum();
+            let mut ungrouped = drawing.stacks.clone();
+            execute_ungrouped(&drawing.instructions, &mut ungrouped);
+            let mut grouped = drawing.stacks.clone();
+            execute_grouped(&drawing.instructions, &mut grouped);
+            let heights = |stacks: &[Vec<char>]| stacks.iter().map(Vec::len).collect::<Vec<_>>();
+            prop_assert_eq!(heights(&ungrouped).iter().sum::<usize>(), total);
+            prop_assert_eq!(heights(&grouped), heights(&ungrouped));
+        }
+    }
+
+    #[test]
+    fn test_day5() {
+        assert_eq!(
+            solve_file::<Day5>(2022, "day5-test.txt"),
+            ("CMZ".into(), "MCD".into())
+        );
+        assert_eq!(
+            solve_file::<Day5>(2022, "day5.txt"),
+            ("SVFDLGLWV".into(), "DCVTCVPCL".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Day5::parse("\n"),
+            Err(Error::TooFewGroups {
+                expected: 1,
+                found: 0
+            })
+        ));
+        let err =
+            Day5::parse("[A]\n 1 \n\nmove 1 from 1 to 1\n\nmove 1 from 1 to 1\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 6, column 1: Expected only stacks and instructions"
+        );
+    }
+}
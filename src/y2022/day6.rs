@@ -1,5 +1,6 @@
-use crate::datastream::*;
-use crate::util::*;
+use super::datastream::*;
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
 
 // --- Day 6: Tuning Trouble ---
 
@@ -50,14 +51,38 @@ use crate::util::*;
 
 // How many characters need to be processed before the first start-of-message marker is detected?
 
-pub fn day6(filename: &str) {
-    let input = read_file(filename);
-
-    // Turn the input into a vector of integers in the 0..26 range.
-    let input: Vec<u8> = input_to_u8s(&input);
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        // Turn the input into a vector of integers in the 0..26 range.
+        let datastream = input_to_u8s(input);
+        // A start-of-message marker is also a start-of-packet marker.
+        if find_start_of_message_marker(14, &datastream).is_none() {
+            return Err(Error::Unsolvable(
+                "No start-of-message marker found".to_string(),
+            ));
+        }
+        Ok(datastream)
+    }
+
+    fn part1(datastream: &Vec<u8>) -> Answer {
+        start_of_packet(datastream).into()
+    }
+
+    fn part2(datastream: &Vec<u8>) -> Answer {
+        start_of_message(datastream).into()
+    }
+}
 
-    let (_, end4) = find_start_of_message_marker(4, &input);
-    let (_, end14) = find_start_of_message_marker(14, &input);
+fn start_of_packet(datastream: &[u8]) -> usize {
+    let (_, end4) = find_start_of_message_marker(4, datastream).expect("Checked by the parser");
+    end4
+}
 
-    println!("Day 6: {}, {}", end4, end14);
+fn start_of_message(datastream: &[u8]) -> usize {
+    let (_, end14) = find_start_of_message_marker(14, datastream).expect("Checked by the parser");
+    end14
 }
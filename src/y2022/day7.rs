@@ -1,5 +1,7 @@
-use crate::util::*;
-use lazy_regex::regex_captures;
+use crate::error::{Error, Result};
+use crate::parse::{self, Span};
+use crate::solution::{Answer, Solution};
+use log::trace;
 use std::collections::HashMap;
 
 // --- Day 7: No Space Left On Device ---
@@ -92,8 +94,7 @@ enum CommandLineLines {
 }
 
 #[derive(Debug)]
-struct Node {
-    node_id: usize,
+pub struct Node {
     name: String,
     size: usize,
     children: Vec<usize>,
@@ -101,13 +102,36 @@ struct Node {
     is_directory: bool,
 }
 
-pub fn day7(filename: &str) {
-    let input = read_file(filename);
+pub struct Day7;
 
-    let cl_lines: Vec<CommandLineLines> = input.lines().map(parse_line).collect();
+impl Solution for Day7 {
+    type Input = HashMap<usize, Node>;
 
-    let mut root = Node {
-        node_id: 0,
+    fn parse(input: &str) -> Result<HashMap<usize, Node>> {
+        build_tree(input)
+    }
+
+    fn part1(nodes: &HashMap<usize, Node>) -> Answer {
+        total_size_of_small_dirs(nodes).into()
+    }
+
+    fn part2(nodes: &HashMap<usize, Node>) -> Answer {
+        smallest_dir_to_delete(nodes).into()
+    }
+}
+
+/// The size of the disk.
+const DISK_SPACE: usize = 70000000;
+/// The free space the update needs.
+const REQUIRED_FREE_SPACE: usize = 30000000;
+
+/// Replay the terminal output to build the file tree, keyed by the line that
+/// introduced each node. The root has id 0.
+fn build_tree(input: &str) -> Result<HashMap<usize, Node>> {
+    let cl_lines: Vec<CommandLineLines> =
+        parse::lines(input).map(parse_line).collect::<Result<_>>()?;
+
+    let root = Node {
         name: "/".to_string(),
         size: 0,
         children: Vec::new(),
@@ -131,7 +155,6 @@ pub fn day7(filename: &str) {
                     let line = &cl_lines[line_index];
                     if let CommandLineLines::File { size, name } = line {
                         let node = Node {
-                            node_id: line_index,
                             name: name.to_string(),
                             size: *size,
                             children: Vec::new(),
@@ -146,7 +169,6 @@ pub fn day7(filename: &str) {
                             .push(line_index);
                     } else if let CommandLineLines::Dir { name } = line {
                         let node = Node {
-                            node_id: line_index,
                             name: name.to_string(),
                             size: 0,
                             children: Vec::new(),
@@ -167,9 +189,15 @@ pub fn day7(filename: &str) {
                 }
             }
             CommandLineLines::Cd(dir) => {
-                if dir == ".." {
+                if dir == "/" {
+                    current_node_id = 0;
+                } else if dir == ".." {
                     // Set the current node to the parent node
-                    current_node_id = nodes.get(&current_node_id).unwrap().parent.unwrap();
+                    current_node_id = nodes
+                        .get(&current_node_id)
+                        .unwrap()
+                        .parent
+                        .ok_or_else(|| Error::parse(line_index, 6, "Can't leave the root"))?;
                 } else {
                     // Set the current node to the child node with the given name
                     current_node_id = nodes
@@ -178,73 +206,89 @@ pub fn day7(filename: &str) {
                         .children
                         .iter()
                         .find(|&&child_id| nodes.get(&child_id).unwrap().name == *dir)
-                        .unwrap()
-                        .clone()
+                        .copied()
+                        .ok_or_else(|| {
+                            Error::parse(line_index, 6, format!("Unknown directory {}", dir))
+                        })?
                 }
             }
             CommandLineLines::File { .. } => {
-                panic!("Unexpected file");
+                return Err(Error::parse(line_index, 1, "Unexpected file"));
             }
             CommandLineLines::Dir { .. } => {
-                panic!("Unexpected dir");
+                return Err(Error::parse(line_index, 1, "Unexpected dir"));
             }
         }
     }
 
     include_content_in_size(&mut nodes, 0);
+    if nodes.get(&0).unwrap().size > DISK_SPACE {
+        return Err(Error::Unsolvable(
+            "The files don't fit on the disk".to_string(),
+        ));
+    }
 
-    // println!("{:#?}", nodes);
+    trace!("{:#?}", nodes);
+
+    Ok(nodes)
+}
 
-    // Find all of the directories with a total size of at most 100000. What is the sum of the total sizes of those directories?
+/// Find all of the directories with a total size of at most 100000. What is the sum of the total sizes of those directories?
+fn total_size_of_small_dirs(nodes: &HashMap<usize, Node>) -> usize {
     let mut total_size = 0;
     for node in nodes.values() {
         if node.is_directory && node.size <= 100000 {
             total_size += node.size;
         }
     }
-    println!("Total size: {}", total_size);
-
-    // Part 2:
-    // The total disk space available to the filesystem is 70000000. To run the update, you need unused space of at least 30000000. You need to find a directory you can delete that will free up enough space to run the update.
-
-    // Find the smallest directory that, if deleted, would free up enough space on the filesystem to run the update. What is the total size of that directory?
-    let required_free_space = 30000000;
-    println!("Required free space: {}", required_free_space);
-    let current_free_space = 70000000 - nodes.get(&0).unwrap().size;
-    println!("Current free space: {}", current_free_space);
-    let required_to_free = required_free_space - current_free_space;
-    let mut smallest_dir = 70000000;
+    total_size
+}
+
+// Part 2:
+// The total disk space available to the filesystem is 70000000. To run the update, you need unused space of at least 30000000. You need to find a directory you can delete that will free up enough space to run the update.
+
+/// Find the smallest directory that, if deleted, would free up enough space on the filesystem to run the update. What is the total size of that directory?
+fn smallest_dir_to_delete(nodes: &HashMap<usize, Node>) -> usize {
+    // The parser checks that the files fit on the disk.
+    let current_free_space = DISK_SPACE - nodes.get(&0).unwrap().size;
+    let required_to_free = REQUIRED_FREE_SPACE.saturating_sub(current_free_space);
+    let mut smallest_dir = DISK_SPACE;
     for node in nodes.values() {
         if node.is_directory && node.size < smallest_dir && node.size >= required_to_free {
             smallest_dir = node.size;
         }
     }
-    println!("Smallest dir: {}", smallest_dir);
+    smallest_dir
 }
 
 fn include_content_in_size(nodes: &mut HashMap<usize, Node>, node_id: usize) {
     let children = nodes.get_mut(&node_id).unwrap().children.clone();
     for child_id in children.iter() {
         include_content_in_size(nodes, *child_id);
-        nodes.get_mut(&node_id).unwrap().size += nodes.get(child_id).unwrap().size;
+        // Saturate, so files too large for the disk are rejected instead of
+        // overflowing.
+        let size = nodes.get(child_id).unwrap().size;
+        let node = nodes.get_mut(&node_id).unwrap();
+        node.size = node.size.saturating_add(size);
     }
 }
 
-fn parse_line(line: &str) -> CommandLineLines {
-    if let Some(_) = regex_captures!(r"\$ ls", line) {
-        CommandLineLines::Ls
-    } else if let Some((_, dir)) = regex_captures!(r"\$ cd (.*)", line) {
-        CommandLineLines::Cd(dir.to_string())
-    } else if let Some((_, size, name)) = regex_captures!(r"(\d+) (.*)", line) {
-        CommandLineLines::File {
-            size: size.parse().unwrap(),
-            name: name.to_string(),
-        }
-    } else if let Some((_, name)) = regex_captures!(r"dir (.*)", line) {
-        CommandLineLines::Dir {
-            name: name.to_string(),
-        }
+/// Parse a line of terminal output.
+fn parse_line(line: Span) -> Result<CommandLineLines> {
+    if line.text == "$ ls" {
+        Ok(CommandLineLines::Ls)
+    } else if let Some(dir) = line.strip_prefix("$ cd ") {
+        Ok(CommandLineLines::Cd(dir.text.to_string()))
+    } else if let Some(name) = line.strip_prefix("dir ") {
+        Ok(CommandLineLines::Dir {
+            name: name.text.to_string(),
+        })
+    } else if let Some((size, name)) = line.split_once(" ") {
+        Ok(CommandLineLines::File {
+            size: size.parse("a file size")?,
+            name: name.text.to_string(),
+        })
     } else {
-        panic!("Unknown line: {}", line);
+        Err(line.error("Unknown line"))
     }
 }
@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+
+use log::trace;
+
+use crate::error::Result;
+use crate::parse;
+use crate::solution::{Answer, Solution};
+use crate::util::{Direction, Grid, Point};
+
+// Day 9: Rope Bridge ---
+
+// This rope bridge creaks as you walk along it. You aren't sure how old it is, or whether it can even support your weight.
+
+// It seems to support the Elves just fine, though. The bridge spans a gorge which was carved out by the massive river far below you.
+
+// You step carefully; as you do, the ropes stretch and twist. You decide to distract yourself by modeling rope physics; maybe you can even figure out where not to step.
+
+// Consider a rope with a knot at each end; these knots mark the head and the tail of the rope. If the head moves far enough away from the tail, the tail is pulled toward the head.
+
+// Due to nebulous reasoning involving Planck lengths, you should be able to model the positions of the knots on a two-dimensional grid. Then, by following a hypothetical series of motions (your puzzle input) for the head, you can determine how the tail will move.
+
+// Due to the aforementioned Planck lengths, the rope must be quite short; in fact, the head (H) and tail (T) must always be touching (diagonally adjacent and even overlapping both count as touching):
+
+// ....
+// .TH.
+// ....
+
+// ....
+// .H..
+// ..T.
+// ....
+
+// ...
+// .H. (H covers T)
+// ...
+
+// If the head is ever two steps directly up, down, left, or right from the tail, the tail must also move one step in that direction so it remains close enough:
+
+// .....    .....    .....
+// .TH.. -> .T.H. -> ..TH.
+// .....    .....    .....
+
+// ...    ...    ...
+// .T.    .T.    ...
+// .H. -> ... -> .T.
+// ...    .H.    .H.
+// ...    ...    ...
+
+// Otherwise, if the head and tail aren't touching and aren't in the same row or column, the tail always moves one step diagonally to keep up:
+
+// .....    .....    .....
+// .....    ..H..    ..H..
+// ..H.. -> ..... -> ..T..
+// .T...    .T...    .....
+// .....    .....    .....
+
+// .....    .....    .....
+// .....    .....    .....
+// ..H.. -> ...H. -> ..TH.
+// .T...    .T...    .....
+// .....    .....    .....
+
+// You just need to work out where the tail goes as the head follows a series of motions. Assume the head and the tail both start at the same position, overlapping.
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<(Direction, usize)>;
+
+    fn parse(input: &str) -> Result<Vec<(Direction, usize)>> {
+        // Parse Instructions: "U 19" is converted to "(Up, 19)"
+        let mut instructions = Vec::new();
+        for line in parse::lines(input) {
+            let (direction, distance) = line.expect_split(" ")?;
+            instructions.push((
+                direction.parse("a direction U, D, L or R")?,
+                distance.parse("a distance")?,
+            ));
+        }
+        Ok(instructions)
+    }
+
+    fn part1(instructions: &Vec<(Direction, usize)>) -> Answer {
+        spaces_seen(instructions, 2).into()
+    }
+
+    fn part2(instructions: &Vec<(Direction, usize)>) -> Answer {
+        spaces_seen(instructions, 10).into()
+    }
+}
+
+/// The knots of a rope, starting with the head. All knots start at the origin.
+#[derive(Debug, Clone)]
+pub struct Rope {
+    pub knots: Vec<Point>,
+}
+
+impl Rope {
+    pub fn new(length: usize) -> Rope {
+        Rope {
+            knots: vec![Point::ORIGIN; length],
+        }
+    }
+
+    pub fn tail(&self) -> Point {
+        *self.knots.last().expect("A rope has at least one knot")
+    }
+
+    /// Move the head one step and let the other knots follow.
+    pub fn step(&mut self, direction: Direction) {
+        self.knots[0] += direction.step();
+        for i in 1..self.knots.len() {
+            self.knots[i] = follow(self.knots[i - 1], self.knots[i]);
+        }
+    }
+}
+
+/// Where a knot moves when the knot in front of it moved to `head`.
+///
+/// If the head is ever two steps directly up, down, left, or right from the
+/// tail, the tail must also move one step in that direction so it remains
+/// close enough. Otherwise, if the head and tail aren't touching and aren't in
+/// the same row or column, the tail always moves one step diagonally to keep
+/// up. Both rules are a step towards the head.
+pub fn follow(head: Point, tail: Point) -> Point {
+    if head.chebyshev(tail) <= 1 {
+        tail
+    } else {
+        tail + (head - tail).signum()
+    }
+}
+
+/// Count the spaces seen by the tail of a rope with the given number of knots.
+fn spaces_seen(instructions: &[(Direction, usize)], knots: usize) -> usize {
+    let mut rope = Rope::new(knots);
+    let mut spaces_seen: HashSet<Point> = HashSet::new();
+    // Add the starting position to the set of spaces seen
+    spaces_seen.insert(rope.tail());
+
+    for &(direction, distance) in instructions {
+        for _ in 0..distance {
+            rope.step(direction);
+            // track the spaces seen by the tail
+            spaces_seen.insert(rope.tail());
+        }
+        trace!(
+            "== {} {} ==\n{}",
+            direction,
+            distance,
+            render(&rope.knots).join("\n")
+        );
+    }
+    spaces_seen.len()
+}
+
+/// Draw the rope like the puzzle does: the head is `H`, the other knots are
+/// numbered (or `T` for a short rope) and `s` is the start.
+fn render(knots: &[Point]) -> Vec<String> {
+    let all = || knots.iter().chain([&Point::ORIGIN]);
+    let min = Point::new(
+        all().map(|knot| knot.x).min().unwrap_or(0),
+        all().map(|knot| knot.y).min().unwrap_or(0),
+    );
+    let max = Point::new(
+        all().map(|knot| knot.x).max().unwrap_or(0),
+        all().map(|knot| knot.y).max().unwrap_or(0),
+    );
+    let size = max - min;
+    let mut grid = Grid::new(size.x as usize + 1, size.y as usize + 1, '.');
+    grid[Point::ORIGIN - min] = 's';
+    // Draw from the tail forward, so knots in front cover the ones behind.
+    for (i, &knot) in knots.iter().enumerate().rev() {
+        grid[knot - min] = match i {
+            0 => 'H',
+            _ if knots.len() == 2 => 'T',
+            _ => char::from_digit(i as u32, 36).unwrap_or('#'),
+        };
+    }
+    grid.render(|&c| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest::sample::select;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_knots_stay_adjacent(
+            length in 2..=10usize,
+            motions in prop::collection::vec((select(Direction::ALL.to_vec()), 1..10usize), 0..50),
+        ) {
+            let mut rope = Rope::new(length);
+            for (direction, distance) in motions {
+                for _ in 0..distance {
+                    rope.step(direction);
+                    for pair in rope.knots.windows(2) {
+                        prop_assert!(pair[0].chebyshev(pair[1]) <= 1, "{:?}", rope.knots);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! The solutions and their building blocks, used from outside the crate.
+
+use aoc2022::runner::{solution, solve};
+use aoc2022::solution::{Answer, Solution};
+use aoc2022::util::{Direction, Point};
+use aoc2022::y2022::datastream::{find_start_of_message_marker, input_to_u8s};
+use aoc2022::y2022::day10::{one_step, Cpu, Instruction};
+use aoc2022::y2022::day4::Day4;
+use aoc2022::y2022::day9::Rope;
+
+#[test]
+fn test_building_blocks() {
+    let stream = input_to_u8s("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+    assert_eq!(find_start_of_message_marker(4, &stream), Some((3, 7)));
+
+    let mut rope = Rope::new(2);
+    rope.step(Direction::Right);
+    rope.step(Direction::Right);
+    assert_eq!(rope.tail(), Point::new(1, 0));
+
+    let mut cpu = Cpu {
+        x: 1,
+        cycle: 0,
+        instr_index: 0,
+        instructions: vec![Instruction::Noop, Instruction::Addx(3)],
+    };
+    one_step(&mut cpu);
+    one_step(&mut cpu);
+    assert_eq!((cpu.x, cpu.cycle), (4, 3));
+}
+
+#[test]
+fn test_solutions() {
+    aoc2022::util::set_year(2022);
+    let input = Day4::parse("2-4,6-8\n2-8,3-7\n").unwrap();
+    assert_eq!(Day4::part1(&input), Answer::Int(1));
+
+    let solved = solve(solution(2022, 6).unwrap(), "day6-test.txt", Some(1)).unwrap();
+    assert_eq!(solved.parts[0].answer, Answer::Int(7));
+}